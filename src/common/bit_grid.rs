@@ -0,0 +1,307 @@
+use crate::common::models::{Direction, Grid, Point};
+use std::fmt::{Display, Formatter};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+// one bit per cell, every row starts on a fresh word so rows can be shifted independently
+#[derive(Clone, Hash, PartialEq, Eq, Debug)]
+pub struct BitGrid {
+    words: Vec<u64>,
+    words_per_row: usize,
+    len_x: usize,
+    len_y: usize,
+}
+
+#[allow(dead_code)]
+impl BitGrid {
+    pub fn new(len_x: usize, len_y: usize) -> Self {
+        let words_per_row = len_x.div_ceil(WORD_BITS);
+        Self {
+            words: vec![0; words_per_row * len_y],
+            words_per_row,
+            len_x,
+            len_y,
+        }
+    }
+    pub fn from_points<'a>(
+        len_x: usize,
+        len_y: usize,
+        points: impl IntoIterator<Item = &'a Point<usize>>,
+    ) -> Self {
+        let mut grid = Self::new(len_x, len_y);
+        for point in points {
+            grid.insert(point);
+        }
+        grid
+    }
+    pub fn len_x(&self) -> usize {
+        self.len_x
+    }
+    pub fn len_y(&self) -> usize {
+        self.len_y
+    }
+    pub fn in_bounds(&self, point: &Point<usize>) -> bool {
+        point.x < self.len_x && point.y < self.len_y
+    }
+    fn index(&self, point: &Point<usize>) -> (usize, u64) {
+        assert!(
+            self.in_bounds(point),
+            "{} is outside of the {}x{} grid",
+            point,
+            self.len_x,
+            self.len_y
+        );
+        (
+            point.y * self.words_per_row + point.x / WORD_BITS,
+            1 << (point.x % WORD_BITS),
+        )
+    }
+    pub fn contains(&self, point: &Point<usize>) -> bool {
+        if !self.in_bounds(point) {
+            return false;
+        }
+        let (word, bit) = self.index(point);
+        self.words[word] & bit != 0
+    }
+    pub fn set(&mut self, point: &Point<usize>, value: bool) {
+        let (word, bit) = self.index(point);
+        if value {
+            self.words[word] |= bit;
+        } else {
+            self.words[word] &= !bit;
+        }
+    }
+    pub fn insert(&mut self, point: &Point<usize>) {
+        self.set(point, true);
+    }
+    pub fn remove(&mut self, point: &Point<usize>) {
+        self.set(point, false);
+    }
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+    pub fn iter(&self) -> impl Iterator<Item = Point<usize>> + '_ {
+        self.words.iter().enumerate().flat_map(move |(i, &word)| {
+            let y = i / self.words_per_row;
+            let x_offset = (i % self.words_per_row) * WORD_BITS;
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(Point::new(x_offset + bit, y))
+            })
+        })
+    }
+
+    fn assert_same_size(&self, other: &Self) {
+        assert_eq!(
+            (self.len_x, self.len_y),
+            (other.len_x, other.len_y),
+            "bit grids must have the same size"
+        );
+    }
+    fn combine_with(&mut self, other: &Self, op: impl Fn(u64, u64) -> u64) {
+        self.assert_same_size(other);
+        for (word, &other) in self.words.iter_mut().zip(&other.words) {
+            *word = op(*word, other);
+        }
+    }
+    pub fn union_with(&mut self, other: &Self) {
+        self.combine_with(other, |a, b| a | b);
+    }
+    pub fn intersect_with(&mut self, other: &Self) {
+        self.combine_with(other, |a, b| a & b);
+    }
+    pub fn difference_with(&mut self, other: &Self) {
+        self.combine_with(other, |a, b| a & !b);
+    }
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        result.union_with(other);
+        result
+    }
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        result.intersect_with(other);
+        result
+    }
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        result.difference_with(other);
+        result
+    }
+    pub fn complement(&self) -> Self {
+        let mut result = self.clone();
+        for word in result.words.iter_mut() {
+            *word = !*word;
+        }
+        result.clear_padding();
+        result
+    }
+
+    fn last_word_mask(&self) -> u64 {
+        match self.len_x % WORD_BITS {
+            0 => u64::MAX,
+            bits => (1 << bits) - 1,
+        }
+    }
+    fn clear_padding(&mut self) {
+        if self.words_per_row == 0 {
+            return;
+        }
+        let mask = self.last_word_mask();
+        for row in self.words.chunks_mut(self.words_per_row) {
+            *row.last_mut().unwrap() &= mask;
+        }
+    }
+    // moves every cell one step in the direction, cells leaving the grid are dropped
+    pub fn shifted(&self, direction: Direction) -> Self {
+        let mut result = Self::new(self.len_x, self.len_y);
+        let row_words = self.words_per_row;
+        if row_words == 0 || self.len_y == 0 {
+            return result;
+        }
+        match direction {
+            Direction::Up => {
+                result.words[..(self.len_y - 1) * row_words]
+                    .copy_from_slice(&self.words[row_words..]);
+            }
+            Direction::Down => {
+                result.words[row_words..]
+                    .copy_from_slice(&self.words[..(self.len_y - 1) * row_words]);
+            }
+            Direction::Right => {
+                for (to, from) in result
+                    .words
+                    .chunks_mut(row_words)
+                    .zip(self.words.chunks(row_words))
+                {
+                    let mut carry = 0;
+                    for (to, &from) in to.iter_mut().zip(from) {
+                        *to = (from << 1) | carry;
+                        carry = from >> (WORD_BITS - 1);
+                    }
+                }
+                result.clear_padding();
+            }
+            Direction::Left => {
+                for (to, from) in result
+                    .words
+                    .chunks_mut(row_words)
+                    .zip(self.words.chunks(row_words))
+                {
+                    let mut carry = 0;
+                    for (to, &from) in to.iter_mut().zip(from).rev() {
+                        *to = (from >> 1) | carry;
+                        carry = from << (WORD_BITS - 1);
+                    }
+                }
+            }
+        }
+        result
+    }
+    // all cells one orthogonal step away from any set cell, e.g. the next BFS frontier
+    pub fn neighbors(&self) -> Self {
+        let mut result = self.shifted(Direction::Up);
+        for direction in [Direction::Down, Direction::Left, Direction::Right] {
+            result.union_with(&self.shifted(direction));
+        }
+        result
+    }
+}
+
+impl Display for BitGrid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.len_y {
+            writeln!(f)?;
+            for x in 0..self.len_x {
+                let c = if self.contains(&Point { x, y }) {
+                    '#'
+                } else {
+                    '.'
+                };
+                write!(f, "{}", c)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[allow(dead_code)]
+impl<T> Grid<T> {
+    pub fn to_bit_grid(&self, mut predicate: impl FnMut(&Point<usize>, &T) -> bool) -> BitGrid {
+        let mut bits = BitGrid::new(self.len_x(), self.len_y());
+        for (point, value) in self.iter() {
+            if predicate(&point, value) {
+                bits.insert(&point);
+            }
+        }
+        bits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bit_grid(input: &str) -> BitGrid {
+        input
+            .parse::<Grid<char>>()
+            .unwrap()
+            .to_bit_grid(|_, &c| c == '#')
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = bit_grid("##.\n...\n..#");
+        let b = bit_grid("#..\n.#.\n..#");
+        assert_eq!(a.union(&b), bit_grid("##.\n.#.\n..#"));
+        assert_eq!(a.intersection(&b), bit_grid("#..\n...\n..#"));
+        assert_eq!(a.difference(&b), bit_grid(".#.\n...\n..."));
+        assert_eq!(a.complement(), bit_grid("..#\n###\n##."));
+        assert_eq!(a.count(), 3);
+        assert_eq!(
+            a.iter().collect::<Vec<_>>(),
+            vec![Point::new(0, 0), Point::new(1, 0), Point::new(2, 2)]
+        );
+        assert!(a.difference(&a).is_empty());
+        assert_eq!(a.to_string(), "\n##.\n...\n..#");
+    }
+
+    #[test]
+    fn test_shifts() {
+        let grid = bit_grid("#..\n.#.\n..#");
+        assert_eq!(grid.shifted(Direction::Up), bit_grid(".#.\n..#\n..."));
+        assert_eq!(grid.shifted(Direction::Down), bit_grid("...\n#..\n.#."));
+        assert_eq!(grid.shifted(Direction::Left), bit_grid("...\n#..\n.#."));
+        assert_eq!(grid.shifted(Direction::Right), bit_grid(".#.\n..#\n..."));
+        assert_eq!(
+            bit_grid("...\n.#.\n...").neighbors(),
+            bit_grid(".#.\n#.#\n.#.")
+        );
+    }
+
+    #[test]
+    fn test_shifts_across_words() {
+        let len_x = 130;
+        let mut grid = BitGrid::new(len_x, 2);
+        for x in [0, 63, 64, 127, 129] {
+            grid.insert(&Point::new(x, 1));
+        }
+        let right: Vec<_> = grid.shifted(Direction::Right).iter().map(|p| p.x).collect();
+        assert_eq!(right, vec![1, 64, 65, 128]);
+        let left: Vec<_> = grid.shifted(Direction::Left).iter().map(|p| p.x).collect();
+        assert_eq!(left, vec![62, 63, 126, 128]);
+        assert_eq!(grid.complement().count(), 2 * len_x - 5);
+        assert!(!grid.contains(&Point::new(130, 1)));
+    }
+}
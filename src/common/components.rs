@@ -0,0 +1,179 @@
+use crate::common::models::{Grid, Point};
+use std::collections::VecDeque;
+
+#[allow(dead_code)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Connectivity {
+    FourWay,
+    EightWay,
+}
+
+impl Connectivity {
+    pub fn neighbors<T>(&self, grid: &Grid<T>, point: &Point<usize>) -> Vec<Point<usize>> {
+        match self {
+            Connectivity::FourWay => grid.four_way_neighbors(point),
+            Connectivity::EightWay => grid.eight_way_neighbors(point),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Debug)]
+pub struct Components {
+    pub labels: Grid<usize>,
+    pub stats: Vec<ComponentStats>,
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Debug)]
+pub struct ComponentStats {
+    pub label: usize,
+    pub cells: Vec<Point<usize>>,
+    pub top_left: Point<usize>,
+    pub bottom_right: Point<usize>,
+}
+
+#[allow(dead_code)]
+impl ComponentStats {
+    fn new(label: usize, cells: Vec<Point<usize>>) -> Self {
+        let top_left = Point {
+            x: cells.iter().map(|p| p.x).min().unwrap(),
+            y: cells.iter().map(|p| p.y).min().unwrap(),
+        };
+        let bottom_right = Point {
+            x: cells.iter().map(|p| p.x).max().unwrap(),
+            y: cells.iter().map(|p| p.y).max().unwrap(),
+        };
+        Self {
+            label,
+            cells,
+            top_left,
+            bottom_right,
+        }
+    }
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+    pub fn width(&self) -> usize {
+        self.bottom_right.x - self.top_left.x + 1
+    }
+    pub fn height(&self) -> usize {
+        self.bottom_right.y - self.top_left.y + 1
+    }
+}
+
+#[allow(dead_code)]
+impl<T> Grid<T> {
+    pub fn flood_fill(
+        &self,
+        start: &Point<usize>,
+        connectivity: Connectivity,
+        mut connected: impl FnMut(&T, &T) -> bool,
+    ) -> Vec<Point<usize>> {
+        let mut visited = Grid::new(self.len_x(), self.len_y());
+        self.fill(start, connectivity, &mut visited, &mut connected)
+    }
+    pub fn label_components(
+        &self,
+        connectivity: Connectivity,
+        mut connected: impl FnMut(&T, &T) -> bool,
+    ) -> Components {
+        let mut visited = Grid::new(self.len_x(), self.len_y());
+        let mut labels = Grid::new(self.len_x(), self.len_y());
+        let mut stats = Vec::new();
+        for (point, _) in self.iter() {
+            if visited[&point] {
+                continue;
+            }
+            let label = stats.len();
+            let cells = self.fill(&point, connectivity, &mut visited, &mut connected);
+            for cell in &cells {
+                labels[cell] = label;
+            }
+            stats.push(ComponentStats::new(label, cells));
+        }
+        Components { labels, stats }
+    }
+    pub fn label_regions(&self, connectivity: Connectivity) -> Components
+    where
+        T: PartialEq,
+    {
+        self.label_components(connectivity, |left, right| left == right)
+    }
+    fn fill(
+        &self,
+        start: &Point<usize>,
+        connectivity: Connectivity,
+        visited: &mut Grid<bool>,
+        connected: &mut impl FnMut(&T, &T) -> bool,
+    ) -> Vec<Point<usize>> {
+        let mut cells = Vec::new();
+        let mut queue = VecDeque::from([*start]);
+        visited[start] = true;
+        while let Some(point) = queue.pop_front() {
+            for next in connectivity.neighbors(self, &point) {
+                if !visited[&next] && connected(&self[&point], &self[&next]) {
+                    visited[&next] = true;
+                    queue.push_back(next);
+                }
+            }
+            cells.push(point);
+        }
+        cells
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(input: &str) -> Grid<char> {
+        Grid::from_iter(input.lines().map(|line| line.chars()))
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let grid = grid("AAB\nBAB\nBBA");
+        let same = |left: &char, right: &char| left == right;
+        let mut cells = grid.flood_fill(&Point::new(0, 0), Connectivity::FourWay, same);
+        cells.sort();
+        assert_eq!(
+            cells,
+            vec![Point::new(0, 0), Point::new(1, 0), Point::new(1, 1)]
+        );
+        assert_eq!(
+            grid.flood_fill(&Point::new(0, 0), Connectivity::EightWay, same)
+                .len(),
+            4
+        );
+
+        let heights = Grid::from_iter([[0, 1, 2], [5, 4, 3]].into_iter());
+        let climb = heights.flood_fill(&Point::new(0, 0), Connectivity::FourWay, |&from, &to| {
+            to == from + 1
+        });
+        assert_eq!(climb.len(), 6);
+    }
+
+    #[test]
+    fn test_label_components() {
+        let grid = grid("AAB\nBAB\nBBA");
+        let components = grid.label_regions(Connectivity::FourWay);
+        assert_eq!(components.stats.len(), 4);
+        assert_eq!(components.labels[&Point::new(1, 1)], 0);
+        assert_eq!(components.labels[&Point::new(2, 0)], 1);
+        assert_eq!(components.labels[&Point::new(0, 1)], 2);
+        assert_eq!(components.labels[&Point::new(1, 2)], 2);
+        assert_eq!(components.labels[&Point::new(2, 2)], 3);
+
+        let b_left = &components.stats[2];
+        assert_eq!(b_left.area(), 3);
+        assert_eq!(
+            (&b_left.top_left, &b_left.bottom_right),
+            (&Point::new(0, 1), &Point::new(1, 2))
+        );
+        assert_eq!((b_left.width(), b_left.height()), (2, 2));
+
+        let diagonal = grid.label_regions(Connectivity::EightWay);
+        assert_eq!(diagonal.stats.len(), 2);
+    }
+}
@@ -0,0 +1,136 @@
+use crate::common::image::Rgb;
+use crate::common::models::{Grid, Point};
+use crate::common::styled::Style;
+use std::fmt::{Display, Formatter};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CellChange<'a, T> {
+    pub point: Point<usize>,
+    pub before: &'a T,
+    pub after: &'a T,
+}
+
+#[allow(dead_code)]
+impl<T: PartialEq> Grid<T> {
+    pub fn diff<'a>(&'a self, other: &'a Grid<T>) -> Vec<CellChange<'a, T>> {
+        assert_eq!(
+            (self.len_x(), self.len_y()),
+            (other.len_x(), other.len_y()),
+            "cannot diff grids of different sizes"
+        );
+        self.iter()
+            .zip(other.iter())
+            .filter(|((_, before), (_, after))| before != after)
+            .map(|((point, before), (_, after))| CellChange {
+                point,
+                before,
+                after,
+            })
+            .collect()
+    }
+    pub fn display_diff<'a>(&'a self, other: &'a Grid<T>) -> GridDiffDisplay<'a, T> {
+        GridDiffDisplay {
+            before: self,
+            after: other,
+        }
+    }
+}
+
+pub struct GridDiffDisplay<'a, T> {
+    before: &'a Grid<T>,
+    after: &'a Grid<T>,
+}
+
+// prints the area around the changed cells of the new grid, then each change
+impl<T: PartialEq + Display> Display for GridDiffDisplay<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let changes = self.before.diff(self.after);
+        if changes.is_empty() {
+            return write!(f, " no changes");
+        }
+        let top_left = Point {
+            x: changes
+                .iter()
+                .map(|c| c.point.x)
+                .min()
+                .unwrap()
+                .saturating_sub(1),
+            y: changes
+                .iter()
+                .map(|c| c.point.y)
+                .min()
+                .unwrap()
+                .saturating_sub(1),
+        };
+        let bottom_right = Point {
+            x: (changes.iter().map(|c| c.point.x).max().unwrap() + 1).min(self.after.len_x() - 1),
+            y: (changes.iter().map(|c| c.point.y).max().unwrap() + 1).min(self.after.len_y() - 1),
+        };
+        let highlight = Style::new().foreground(Rgb::BLACK).background(Rgb::YELLOW);
+
+        write!(
+            f,
+            "\n{} cells changed around {}..={}",
+            changes.len(),
+            top_left,
+            bottom_right
+        )?;
+        for y in top_left.y..=bottom_right.y {
+            writeln!(f)?;
+            for x in top_left.x..=bottom_right.x {
+                let point = Point { x, y };
+                let value = &self.after[&point];
+                if self.before[&point] == *value {
+                    write!(f, "{}", value)?;
+                } else {
+                    write!(f, "{}", highlight.paint(value))?;
+                }
+            }
+        }
+        for change in &changes {
+            write!(
+                f,
+                "\n{}: {} -> {}",
+                change.point, change.before, change.after
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let before: Grid<char> = "#####\n#.@O.\n#####".parse().unwrap();
+        let after: Grid<char> = "#####\n#..@O\n#####".parse().unwrap();
+        let changes = before.diff(&after);
+        assert_eq!(
+            changes
+                .iter()
+                .map(|c| (c.point, *c.before, *c.after))
+                .collect::<Vec<_>>(),
+            vec![
+                (Point::new(2, 1), '@', '.'),
+                (Point::new(3, 1), 'O', '@'),
+                (Point::new(4, 1), '.', 'O'),
+            ]
+        );
+        assert!(before.diff(&before).is_empty());
+    }
+
+    #[test]
+    fn test_display_diff() {
+        let before: Grid<char> = "......\n......\n..@...\n......".parse().unwrap();
+        let after: Grid<char> = "......\n......\n...@..\n......".parse().unwrap();
+        let diff = before.display_diff(&after).to_string();
+        let escapes = regex::Regex::new("\x1b\\[[0-9;]*m").unwrap();
+        assert_eq!(
+            escapes.replace_all(&diff, ""),
+            "\n2 cells changed around (1, 1)..=(4, 3)\n....\n..@.\n....\n(2, 2): @ -> .\n(3, 2): . -> @"
+        );
+        assert_eq!(before.display_diff(&before).to_string(), " no changes");
+    }
+}
@@ -0,0 +1,155 @@
+use crate::common::models::{Grid, Point};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+#[derive(Clone, Debug)]
+pub struct DistanceField {
+    pub distances: Grid<Option<usize>>,
+    parents: Grid<Option<Point<usize>>>,
+}
+
+#[allow(dead_code)]
+impl DistanceField {
+    fn new(len_x: usize, len_y: usize) -> Self {
+        Self {
+            distances: Grid::new(len_x, len_y),
+            parents: Grid::new(len_x, len_y),
+        }
+    }
+    pub fn get(&self, point: &Point<usize>) -> Option<usize> {
+        self.distances.get(point).copied().flatten()
+    }
+    pub fn reachable(&self) -> impl Iterator<Item = (Point<usize>, usize)> + '_ {
+        self.distances
+            .iter()
+            .filter_map(|(point, distance)| distance.map(|distance| (point, distance)))
+    }
+    pub fn path_to(&self, target: &Point<usize>) -> Option<Vec<Point<usize>>> {
+        self.get(target)?;
+        let mut path = vec![*target];
+        while let Some(parent) = &self.parents[path.last().unwrap()] {
+            path.push(*parent);
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+#[allow(dead_code)]
+impl<T> Grid<T> {
+    pub fn distances_from(
+        &self,
+        source: &Point<usize>,
+        passable: impl FnMut(&Point<usize>, &T) -> bool,
+    ) -> DistanceField {
+        self.distances_from_many([*source], passable)
+    }
+    pub fn distances_from_many(
+        &self,
+        sources: impl IntoIterator<Item = Point<usize>>,
+        mut passable: impl FnMut(&Point<usize>, &T) -> bool,
+    ) -> DistanceField {
+        let mut field = DistanceField::new(self.len_x(), self.len_y());
+        let mut queue = VecDeque::new();
+        for source in sources {
+            if field.distances[&source].is_none() {
+                field.distances[&source] = Some(0);
+                queue.push_back(source);
+            }
+        }
+        while let Some(point) = queue.pop_front() {
+            let distance = field.distances[&point].unwrap() + 1;
+            for next in self.four_way_neighbors(&point) {
+                if field.distances[&next].is_none() && passable(&next, &self[&next]) {
+                    field.distances[&next] = Some(distance);
+                    field.parents[&next] = Some(point);
+                    queue.push_back(next);
+                }
+            }
+        }
+        field
+    }
+    pub fn weighted_distances_from_many(
+        &self,
+        sources: impl IntoIterator<Item = Point<usize>>,
+        mut cost: impl FnMut(&Point<usize>, &T) -> Option<usize>,
+    ) -> DistanceField {
+        let mut field = DistanceField::new(self.len_x(), self.len_y());
+        let mut queue = BinaryHeap::new();
+        for source in sources {
+            field.distances[&source] = Some(0);
+            queue.push(Reverse((0, source)));
+        }
+        while let Some(Reverse((distance, point))) = queue.pop() {
+            if field.distances[&point].is_some_and(|known| known < distance) {
+                continue;
+            }
+            for next in self.four_way_neighbors(&point) {
+                let Some(step) = cost(&next, &self[&next]) else {
+                    continue;
+                };
+                let next_distance = distance + step;
+                if field.distances[&next].is_none_or(|known| next_distance < known) {
+                    field.distances[&next] = Some(next_distance);
+                    field.parents[&next] = Some(point);
+                    queue.push(Reverse((next_distance, next)));
+                }
+            }
+        }
+        field
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn maze() -> Grid<char> {
+        Grid::from_iter(
+            ["S..#", ".#.#", ".#..", "...E"]
+                .into_iter()
+                .map(|row| row.chars()),
+        )
+    }
+
+    #[test]
+    fn test_distances_and_path() {
+        let maze = maze();
+        let field = maze.distances_from(&Point::new(0, 0), |_, &c| c != '#');
+        assert_eq!(field.get(&Point::new(3, 3)), Some(6));
+        assert_eq!(field.get(&Point::new(3, 0)), None);
+        assert_eq!(field.get(&Point::new(9, 9)), None);
+        let path = field.path_to(&Point::new(2, 2)).unwrap();
+        assert_eq!(path.first(), Some(&Point::new(0, 0)));
+        assert_eq!(path.len(), 5);
+        assert!(path.windows(2).all(|w| w[0].manhattan_distance(&w[1]) == 1));
+        assert!(field.path_to(&Point::new(1, 1)).is_none());
+    }
+
+    #[test]
+    fn test_multiple_sources() {
+        let maze = maze();
+        let field =
+            maze.distances_from_many([Point::new(0, 0), Point::new(3, 3)], |_, &c| c != '#');
+        assert_eq!(field.get(&Point::new(0, 3)), Some(3));
+        assert_eq!(field.get(&Point::new(2, 0)), Some(2));
+        assert_eq!(field.reachable().count(), 12);
+    }
+
+    #[test]
+    fn test_weighted_distances() {
+        let costs = Grid::from_iter([[1, 9, 1], [1, 1, 1]].into_iter());
+        let field = costs.weighted_distances_from_many([Point::new(0, 0)], |_, &c| Some(c));
+        assert_eq!(field.get(&Point::new(2, 0)), Some(4));
+        assert_eq!(
+            field.path_to(&Point::new(2, 0)).unwrap(),
+            vec![
+                Point::new(0, 0),
+                Point::new(0, 1),
+                Point::new(1, 1),
+                Point::new(2, 1),
+                Point::new(2, 0)
+            ]
+        );
+    }
+}
@@ -0,0 +1,187 @@
+use crate::common::models::{Grid, Point};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone)]
+pub struct Dsu {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    size: Vec<usize>,
+    components: usize,
+}
+
+#[allow(dead_code)]
+impl Dsu {
+    pub fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+            rank: vec![0; len],
+            size: vec![1; len],
+            components: len,
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut current = element;
+        while self.parent[current] != root {
+            current = std::mem::replace(&mut self.parent[current], root);
+        }
+        root
+    }
+    pub fn union(&mut self, left: usize, right: usize) -> bool {
+        let (left, right) = (self.find(left), self.find(right));
+        if left == right {
+            return false;
+        }
+        let (root, child) = if self.rank[left] < self.rank[right] {
+            (right, left)
+        } else {
+            (left, right)
+        };
+        if self.rank[root] == self.rank[child] {
+            self.rank[root] += 1;
+        }
+        self.parent[child] = root;
+        self.size[root] += self.size[child];
+        self.components -= 1;
+        true
+    }
+    pub fn connected(&mut self, left: usize, right: usize) -> bool {
+        self.find(left) == self.find(right)
+    }
+    pub fn component_size(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.size[root]
+    }
+    pub fn components(&self) -> usize {
+        self.components
+    }
+    pub fn groups(&mut self) -> Vec<Vec<usize>> {
+        let mut groups: HashMap<usize, Vec<usize>> = HashMap::new();
+        for element in 0..self.len() {
+            groups.entry(self.find(element)).or_default().push(element);
+        }
+        let mut groups: Vec<_> = groups.into_values().collect();
+        groups.sort();
+        groups
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GridDsu {
+    len_x: usize,
+    dsu: Dsu,
+}
+
+#[allow(dead_code)]
+impl GridDsu {
+    pub fn new(len_x: usize, len_y: usize) -> Self {
+        Self {
+            len_x,
+            dsu: Dsu::new(len_x * len_y),
+        }
+    }
+    pub fn for_grid<T>(grid: &Grid<T>) -> Self {
+        Self::new(grid.len_x(), grid.len_y())
+    }
+    pub fn find(&mut self, point: &Point<usize>) -> usize {
+        let index = self.index(point);
+        self.dsu.find(index)
+    }
+    pub fn union(&mut self, left: &Point<usize>, right: &Point<usize>) -> bool {
+        let (left, right) = (self.index(left), self.index(right));
+        self.dsu.union(left, right)
+    }
+    pub fn connected(&mut self, left: &Point<usize>, right: &Point<usize>) -> bool {
+        self.find(left) == self.find(right)
+    }
+    pub fn component_size(&mut self, point: &Point<usize>) -> usize {
+        let index = self.index(point);
+        self.dsu.component_size(index)
+    }
+    pub fn components(&self) -> usize {
+        self.dsu.components()
+    }
+    pub fn groups(&mut self) -> Vec<Vec<Point<usize>>> {
+        let len_x = self.len_x;
+        self.dsu
+            .groups()
+            .into_iter()
+            .map(|group| {
+                group
+                    .into_iter()
+                    .map(|index| Point::new(index % len_x, index / len_x))
+                    .collect()
+            })
+            .collect()
+    }
+    fn index(&self, point: &Point<usize>) -> usize {
+        assert!(point.x < self.len_x, "{} is outside the grid", point);
+        point.y * self.len_x + point.x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_union_and_sizes() {
+        let mut dsu = Dsu::new(6);
+        assert!(dsu.union(0, 1));
+        assert!(dsu.union(2, 3));
+        assert!(dsu.union(1, 3));
+        assert!(!dsu.union(0, 2));
+        assert!(dsu.connected(0, 3));
+        assert!(!dsu.connected(0, 4));
+        assert_eq!(dsu.component_size(2), 4);
+        assert_eq!(dsu.component_size(5), 1);
+        assert_eq!(dsu.components(), 3);
+        assert_eq!(dsu.groups(), vec![vec![0, 1, 2, 3], vec![4], vec![5]]);
+    }
+
+    #[test]
+    fn test_long_chain_is_compressed() {
+        let mut dsu = Dsu::new(10_000);
+        for i in 1..10_000 {
+            dsu.union(i - 1, i);
+        }
+        assert_eq!(dsu.component_size(0), 10_000);
+
+        // union by rank never builds a deep tree, so link a chain 0 -> 1 -> ... -> 9999 by hand
+        let mut dsu = Dsu::new(10_000);
+        for i in 0..9_999 {
+            dsu.parent[i] = i + 1;
+        }
+        let root = 9_999;
+        for i in 0..10_000 {
+            assert_eq!(dsu.find(i), root);
+        }
+        assert!(dsu.parent.iter().all(|&parent| parent == root));
+    }
+
+    #[test]
+    fn test_grid_dsu() {
+        let grid: Grid<char> = Grid::from_str_with("AAB\nABB\nCCB", |c, _| Ok(Some(c))).unwrap();
+        let mut dsu = GridDsu::for_grid(&grid);
+        for (point, value) in grid.iter() {
+            for neighbor in grid.four_way_neighbors(&point) {
+                if grid.get(&neighbor) == Some(value) {
+                    dsu.union(&point, &neighbor);
+                }
+            }
+        }
+        assert_eq!(dsu.components(), 3);
+        assert!(dsu.connected(&Point::new(0, 0), &Point::new(0, 1)));
+        assert!(!dsu.connected(&Point::new(0, 1), &Point::new(0, 2)));
+        assert_eq!(dsu.component_size(&Point::new(2, 2)), 4);
+    }
+}
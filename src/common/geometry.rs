@@ -0,0 +1,252 @@
+use crate::common::components::ComponentStats;
+use crate::common::models::{Direction, Point};
+use std::collections::{BTreeSet, HashSet};
+
+// a set of unit cells; cell (x, y) covers the square from vertex (x, y) to (x + 1, y + 1)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Region {
+    cells: HashSet<Point<usize>>,
+}
+
+#[allow(dead_code)]
+impl Region {
+    pub fn new(cells: impl IntoIterator<Item = Point<usize>>) -> Self {
+        Self {
+            cells: cells.into_iter().collect(),
+        }
+    }
+    pub fn contains(&self, point: &Point<usize>) -> bool {
+        self.cells.contains(point)
+    }
+    fn contains_offset(&self, point: &Point<usize>, dx: i64, dy: i64) -> bool {
+        match (
+            point.x.checked_add_signed(dx as isize),
+            point.y.checked_add_signed(dy as isize),
+        ) {
+            (Some(x), Some(y)) => self.cells.contains(&Point { x, y }),
+            _ => false,
+        }
+    }
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+    pub fn perimeter(&self) -> usize {
+        self.boundary_edges().len()
+    }
+    // every straight side ends in exactly one corner, so count convex and concave corners
+    pub fn sides(&self) -> usize {
+        self.cells
+            .iter()
+            .map(|cell| {
+                [(-1, -1), (1, -1), (1, 1), (-1, 1)]
+                    .into_iter()
+                    .filter(|&(dx, dy)| {
+                        let horizontal = self.contains_offset(cell, dx, 0);
+                        let vertical = self.contains_offset(cell, 0, dy);
+                        let diagonal = self.contains_offset(cell, dx, dy);
+                        (!horizontal && !vertical) || (horizontal && vertical && !diagonal)
+                    })
+                    .count()
+            })
+            .sum()
+    }
+    pub fn holes(&self) -> usize {
+        self.hole_outlines().len()
+    }
+    // the outlines of the holes only, the outer boundary winds the other way
+    pub fn hole_outlines(&self) -> Vec<Vec<Point<usize>>> {
+        self.outlines()
+            .into_iter()
+            .filter(|outline| signed_area(outline) < 0)
+            .collect()
+    }
+
+    // unit edges walked clockwise around the cells, keeping the region on the right
+    fn boundary_edges(&self) -> BTreeSet<(Point<usize>, Direction)> {
+        let mut edges = BTreeSet::new();
+        for cell in &self.cells {
+            let Point { x, y } = *cell;
+            if !self.contains_offset(cell, 0, -1) {
+                edges.insert((Point::new(x, y), Direction::Right));
+            }
+            if !self.contains_offset(cell, 1, 0) {
+                edges.insert((Point::new(x + 1, y), Direction::Down));
+            }
+            if !self.contains_offset(cell, 0, 1) {
+                edges.insert((Point::new(x + 1, y + 1), Direction::Left));
+            }
+            if !self.contains_offset(cell, -1, 0) {
+                edges.insert((Point::new(x, y + 1), Direction::Up));
+            }
+        }
+        edges
+    }
+    // closed polygons of corner vertices: outer boundaries run clockwise and come first,
+    // holes run anticlockwise; cells touching only diagonally share one outline that
+    // passes their common corner twice
+    pub fn outlines(&self) -> Vec<Vec<Point<usize>>> {
+        let edges = self.boundary_edges();
+        let mut remaining = edges.clone();
+        let mut outlines = Vec::new();
+        while let Some(&first) = remaining.first() {
+            let mut vertices = Vec::new();
+            let (mut vertex, mut direction) = first;
+            loop {
+                remaining.remove(&(vertex, direction));
+                vertex = vertex.move_to(direction).unwrap();
+                // turning left first hugs the outside cell, so diagonal gaps keep holes apart
+                let next = [direction.turn_left(), direction, direction.turn_right()]
+                    .into_iter()
+                    .find(|next| edges.contains(&(vertex, *next)))
+                    .expect("boundary edges always form closed loops");
+                if next != direction {
+                    vertices.push(vertex);
+                }
+                direction = next;
+                if (vertex, direction) == first {
+                    break;
+                }
+            }
+            let top_left = (0..vertices.len())
+                .min_by_key(|&i| (vertices[i].y, vertices[i].x))
+                .unwrap();
+            vertices.rotate_left(top_left);
+            outlines.push(vertices);
+        }
+        outlines.sort_by_key(|outline| signed_area(outline) < 0);
+        outlines
+    }
+}
+
+// twice the enclosed area, positive for clockwise outlines with y pointing down
+fn signed_area(outline: &[Point<usize>]) -> i64 {
+    outline
+        .iter()
+        .zip(outline.iter().cycle().skip(1))
+        .map(|(a, b)| a.x as i64 * b.y as i64 - b.x as i64 * a.y as i64)
+        .sum()
+}
+
+#[allow(dead_code)]
+impl ComponentStats {
+    pub fn region(&self) -> Region {
+        Region::new(self.cells.iter().copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::components::Connectivity;
+    use crate::common::models::Grid;
+
+    fn regions(input: &str) -> Vec<(char, Region)> {
+        let grid: Grid<char> = input.parse().unwrap();
+        grid.label_regions(Connectivity::FourWay)
+            .stats
+            .iter()
+            .map(|component| (grid[&component.cells[0]], component.region()))
+            .collect()
+    }
+
+    fn measures(region: &Region) -> (usize, usize, usize, usize) {
+        (
+            region.area(),
+            region.perimeter(),
+            region.sides(),
+            region.holes(),
+        )
+    }
+
+    #[test]
+    fn test_simple_shapes() {
+        let regions = regions("AAAA\nBBCD\nBBCC\nEEEC");
+        let measured: Vec<_> = regions
+            .iter()
+            .map(|(name, region)| (*name, measures(region)))
+            .collect();
+        assert_eq!(
+            measured,
+            vec![
+                ('A', (4, 10, 4, 0)),
+                ('B', (4, 8, 4, 0)),
+                ('C', (4, 10, 8, 0)),
+                ('D', (1, 4, 4, 0)),
+                ('E', (3, 8, 4, 0)),
+            ]
+        );
+        let c = &regions[2].1;
+        assert_eq!(
+            c.outlines(),
+            vec![vec![
+                Point::new(2, 1),
+                Point::new(3, 1),
+                Point::new(3, 2),
+                Point::new(4, 2),
+                Point::new(4, 4),
+                Point::new(3, 4),
+                Point::new(3, 3),
+                Point::new(2, 3),
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_nested_regions() {
+        let nested = regions("AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA");
+        let (name, outer) = &nested[0];
+        assert_eq!(*name, 'A');
+        assert_eq!(measures(outer), (28, 40, 12, 2));
+        let outlines = outer.outlines();
+        assert_eq!(outlines.len(), 3);
+        assert_eq!(
+            outlines[0],
+            vec![
+                Point::new(0, 0),
+                Point::new(6, 0),
+                Point::new(6, 6),
+                Point::new(0, 6)
+            ]
+        );
+        assert!(outlines[1..].iter().all(|hole| signed_area(hole) < 0));
+        assert_eq!(outer.hole_outlines(), outlines[1..]);
+        assert_eq!(
+            outlines.iter().map(|outline| outline.len()).sum::<usize>(),
+            outer.sides()
+        );
+
+        // a ring inside a ring inside a ring
+        let rings = regions("AAAAA\nABBBA\nABCBA\nABBBA\nAAAAA");
+        let measured: Vec<_> = rings.iter().map(|(_, region)| measures(region)).collect();
+        assert_eq!(measured, vec![(16, 32, 8, 1), (8, 16, 8, 1), (1, 4, 4, 0)]);
+    }
+
+    #[test]
+    fn test_diagonal_touching() {
+        // cells touching only at a corner are walked as one figure eight
+        let region = Region::new([Point::new(0, 0), Point::new(1, 1)]);
+        assert_eq!(measures(&region), (2, 8, 8, 0));
+        assert_eq!(
+            region.outlines(),
+            vec![vec![
+                Point::new(0, 0),
+                Point::new(1, 0),
+                Point::new(1, 1),
+                Point::new(2, 1),
+                Point::new(2, 2),
+                Point::new(1, 2),
+                Point::new(1, 1),
+                Point::new(0, 1),
+            ]]
+        );
+
+        // a hole that only touches the outside at a corner is still a hole
+        let region = Region::new(
+            [(0, 0), (1, 0), (2, 0), (0, 1), (2, 1), (0, 2), (1, 2)]
+                .into_iter()
+                .map(|(x, y)| Point::new(x, y)),
+        );
+        assert_eq!(measures(&region), (7, 16, 10, 1));
+        assert_eq!(region.outlines().len(), 2);
+    }
+}
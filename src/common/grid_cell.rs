@@ -0,0 +1,109 @@
+use crate::common::models::{Grid, Point};
+use crate::common::parse_error::ParseError;
+use std::str::FromStr;
+
+pub trait GridCell: Sized {
+    fn from_char(c: char) -> Option<Self>;
+    fn to_char(&self) -> char;
+    // shown next to the character in snapshot legends
+    fn name(&self) -> Option<&'static str> {
+        None
+    }
+    fn parse_char(s: &str, point: &Point<usize>, c: char) -> Result<Self, ParseError> {
+        Self::from_char(c).ok_or_else(|| ParseError::invalid_char(s, point, c))
+    }
+}
+
+impl GridCell for char {
+    fn from_char(c: char) -> Option<Self> {
+        Some(c)
+    }
+    fn to_char(&self) -> char {
+        *self
+    }
+}
+
+// implements GridCell and Display for a fieldless enum from a variant => char table
+macro_rules! grid_cell {
+    ($cell:ident { $($variant:ident => $c:literal),+ $(,)? }) => {
+        impl $crate::common::grid_cell::GridCell for $cell {
+            fn from_char(c: char) -> Option<Self> {
+                match c {
+                    $($c => Some($cell::$variant),)+
+                    _ => None,
+                }
+            }
+            fn to_char(&self) -> char {
+                match self {
+                    $($cell::$variant => $c,)+
+                }
+            }
+            fn name(&self) -> Option<&'static str> {
+                match self {
+                    $($cell::$variant => Some(stringify!($variant)),)+
+                }
+            }
+        }
+
+        impl std::fmt::Display for $cell {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", $crate::common::grid_cell::GridCell::to_char(self))
+            }
+        }
+    };
+}
+pub(crate) use grid_cell;
+
+impl<T: GridCell + Default> FromStr for Grid<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::from_str_with(s, |c, point| T::parse_char(s, point, c).map(Some))
+    }
+}
+
+#[allow(dead_code)]
+impl<T: GridCell> Grid<T> {
+    pub fn to_text(&self) -> String {
+        self.rows()
+            .map(|row| row.iter().map(T::to_char).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    enum Tile {
+        #[default]
+        Open,
+        Wall,
+        Crate,
+    }
+
+    grid_cell!(Tile {
+        Open => '.',
+        Wall => '#',
+        Crate => 'O',
+    });
+
+    #[test]
+    fn test_round_trip() {
+        let text = "#####\n#.O.#\n#####";
+        let grid: Grid<Tile> = text.parse().unwrap();
+        assert_eq!(grid[&Point::new(2, 1)], Tile::Crate);
+        assert_eq!(grid.to_text(), text);
+        assert_eq!(grid.to_string().trim_start(), text);
+        assert_eq!(text.parse::<Grid<char>>().unwrap().to_text(), text);
+    }
+
+    #[test]
+    fn test_invalid_char() {
+        let err = "#.\n#x".parse::<Grid<Tile>>().unwrap_err();
+        assert!(err.to_string().contains('x'));
+        assert_eq!(Tile::from_char('@'), None);
+    }
+}
@@ -0,0 +1,268 @@
+use crate::common::models::{Grid, Point};
+use anyhow::Context as AnyhowContext;
+use std::collections::HashMap;
+use std::path::Path;
+
+#[derive(Copy, Clone, Default, Hash, PartialEq, Eq, Debug)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+#[allow(dead_code)]
+impl Rgb {
+    pub const BLACK: Rgb = Rgb::new(0, 0, 0);
+    pub const WHITE: Rgb = Rgb::new(255, 255, 255);
+    pub const GRAY: Rgb = Rgb::new(128, 128, 128);
+    pub const RED: Rgb = Rgb::new(220, 50, 47);
+    pub const GREEN: Rgb = Rgb::new(133, 153, 0);
+    pub const BLUE: Rgb = Rgb::new(38, 139, 210);
+    pub const YELLOW: Rgb = Rgb::new(181, 137, 0);
+
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+    // spreads hues by the golden ratio so neighbouring labels get distinct colors
+    pub fn from_index(index: usize) -> Self {
+        let hue = (index as f64 * 0.618_033_988_75).fract();
+        Self::from_hsv(hue, 0.65, 0.95)
+    }
+    pub fn from_hsv(hue: f64, saturation: f64, value: f64) -> Self {
+        let sector = (hue.rem_euclid(1.0) * 6.0).floor();
+        let fraction = hue.rem_euclid(1.0) * 6.0 - sector;
+        let p = value * (1.0 - saturation);
+        let q = value * (1.0 - fraction * saturation);
+        let t = value * (1.0 - (1.0 - fraction) * saturation);
+        let (r, g, b) = match sector as u8 {
+            0 => (value, t, p),
+            1 => (q, value, p),
+            2 => (p, value, t),
+            3 => (p, q, value),
+            4 => (t, p, value),
+            _ => (value, p, q),
+        };
+        Self::new(to_u8(r), to_u8(g), to_u8(b))
+    }
+    pub fn lerp(&self, other: &Self, t: f64) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |from: u8, to: u8| (from as f64 + (to as f64 - from as f64) * t).round() as u8;
+        Self::new(
+            mix(self.r, other.r),
+            mix(self.g, other.g),
+            mix(self.b, other.b),
+        )
+    }
+}
+
+fn to_u8(channel: f64) -> u8 {
+    (channel * 255.0).round().clamp(0.0, 255.0) as u8
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+#[allow(dead_code)]
+impl Image {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![Rgb::BLACK; width * height],
+        }
+    }
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    pub fn get(&self, x: usize, y: usize) -> Option<Rgb> {
+        (x < self.width && y < self.height).then(|| self.pixels[y * self.width + x])
+    }
+    pub fn set(&mut self, x: usize, y: usize, color: Rgb) {
+        assert!(
+            x < self.width && y < self.height,
+            "({}, {}) is outside the image",
+            x,
+            y
+        );
+        self.pixels[y * self.width + x] = color;
+    }
+    pub fn fill_rect(&mut self, x: usize, y: usize, width: usize, height: usize, color: Rgb) {
+        for y in y..y + height {
+            for x in x..x + width {
+                self.set(x, y, color);
+            }
+        }
+    }
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut bytes = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        bytes.extend(self.pixels.iter().flat_map(|p| [p.r, p.g, p.b]));
+        bytes
+    }
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut header = Vec::with_capacity(13);
+        header.extend((self.width as u32).to_be_bytes());
+        header.extend((self.height as u32).to_be_bytes());
+        // 8-bit truecolor, default compression, filter and no interlacing
+        header.extend([8, 2, 0, 0, 0]);
+
+        let mut scanlines = Vec::with_capacity(self.height * (self.width * 3 + 1));
+        for row in self.pixels.chunks(self.width.max(1)).take(self.height) {
+            scanlines.push(0);
+            scanlines.extend(row.iter().flat_map(|p| [p.r, p.g, p.b]));
+        }
+
+        let mut bytes = b"\x89PNG\r\n\x1a\n".to_vec();
+        write_chunk(&mut bytes, b"IHDR", &header);
+        write_chunk(&mut bytes, b"IDAT", &zlib_stored(&scanlines));
+        write_chunk(&mut bytes, b"IEND", &[]);
+        bytes
+    }
+    pub fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        let bytes = match path.extension().and_then(|e| e.to_str()) {
+            Some("png") => self.to_png(),
+            Some("ppm") => self.to_ppm(),
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "unsupported image format {}",
+                    path.display()
+                ))
+            }
+        };
+        std::fs::write(path, bytes).with_context(|| format!("failed to write {}", path.display()))
+    }
+}
+
+fn write_chunk(bytes: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    bytes.extend((data.len() as u32).to_be_bytes());
+    let start = bytes.len();
+    bytes.extend(kind);
+    bytes.extend(data);
+    let crc = crc32(&bytes[start..]);
+    bytes.extend(crc.to_be_bytes());
+}
+
+// deflate with uncompressed blocks only; debug images don't need to be small
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = u16::MAX as usize;
+    let mut bytes = vec![0x78, 0x01];
+    let mut blocks = data.chunks(MAX_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        bytes.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        bytes.push(u8::from(blocks.peek().is_none()));
+        let len = block.len() as u16;
+        bytes.extend(len.to_le_bytes());
+        bytes.extend((!len).to_le_bytes());
+        bytes.extend(block);
+    }
+    bytes.extend(adler32(data).to_be_bytes());
+    bytes
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = u32::MAX;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+#[allow(dead_code)]
+impl<T> Grid<T> {
+    pub fn to_image(&self, scale: usize, color: impl FnMut(&Point<usize>, &T) -> Rgb) -> Image {
+        self.to_image_with_overrides(scale, color, HashMap::new())
+    }
+    pub fn to_image_with_overrides(
+        &self,
+        scale: usize,
+        mut color: impl FnMut(&Point<usize>, &T) -> Rgb,
+        overrides: HashMap<&Point<usize>, &Rgb>,
+    ) -> Image {
+        let mut image = Image::new(self.len_x() * scale, self.len_y() * scale);
+        for (point, value) in self.iter() {
+            let color = match overrides.get(&point) {
+                Some(&&color) => color,
+                None => color(&point, value),
+            };
+            image.fill_rect(point.x * scale, point.y * scale, scale, scale, color);
+        }
+        image
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scaled_grid_with_overrides() {
+        let grid = Grid::from_iter([[true, false], [false, true]].into_iter());
+        let path = Point::new(1, 0);
+        let image = grid.to_image_with_overrides(
+            2,
+            |_, &wall| if wall { Rgb::WHITE } else { Rgb::BLACK },
+            [(&path, &Rgb::RED)].into_iter().collect(),
+        );
+        assert_eq!((image.width(), image.height()), (4, 4));
+        assert_eq!(image.get(1, 1), Some(Rgb::WHITE));
+        assert_eq!(image.get(3, 0), Some(Rgb::RED));
+        assert_eq!(image.get(0, 3), Some(Rgb::BLACK));
+        assert_eq!(image.get(4, 0), None);
+
+        let ppm = image.to_ppm();
+        assert!(ppm.starts_with(b"P6\n4 4\n255\n"));
+        assert_eq!(ppm.len(), 11 + 4 * 4 * 3);
+    }
+
+    #[test]
+    fn test_png_encoding() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+
+        let mut image = Image::new(3, 2);
+        image.set(2, 1, Rgb::BLUE);
+        let png = image.to_png();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+        assert!(png.ends_with(&[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]));
+        // scanlines: 2 rows of filter byte + 3 pixels in a single stored block
+        let idat = png.windows(4).position(|w| w == b"IDAT").unwrap();
+        let length = u32::from_be_bytes(png[idat - 4..idat].try_into().unwrap());
+        assert_eq!(length as usize, 2 + 5 + 2 * (1 + 3 * 3) + 4);
+
+        let big = zlib_stored(&vec![7; 70_000]);
+        assert_eq!(big.len(), 2 + 2 * 5 + 70_000 + 4);
+    }
+
+    #[test]
+    fn test_palette() {
+        assert_eq!(Rgb::from_hsv(0.0, 1.0, 1.0), Rgb::new(255, 0, 0));
+        assert_eq!(Rgb::from_hsv(1.0 / 3.0, 1.0, 1.0), Rgb::new(0, 255, 0));
+        assert_ne!(Rgb::from_index(0), Rgb::from_index(1));
+        assert_eq!(Rgb::BLACK.lerp(&Rgb::WHITE, 0.5), Rgb::new(128, 128, 128));
+    }
+}
@@ -0,0 +1,210 @@
+use crate::common::models::{Direction, Grid, Point};
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Corridor {
+    pub from: usize,
+    pub to: usize,
+    pub length: usize,
+    pub turns: usize,
+    pub start_direction: Direction,
+    pub end_direction: Direction,
+    cells: Vec<Point<usize>>,
+}
+
+#[allow(dead_code)]
+impl Corridor {
+    // every cell from the starting junction up to and including the end junction
+    pub fn cells(&self) -> &[Point<usize>] {
+        &self.cells
+    }
+    pub fn steps(&self) -> impl Iterator<Item = (Direction, Point<usize>)> + '_ {
+        self.cells
+            .windows(2)
+            .map(|pair| (direction_between(&pair[0], &pair[1]), pair[1]))
+    }
+}
+
+fn direction_between(from: &Point<usize>, to: &Point<usize>) -> Direction {
+    Direction::directions()
+        .into_iter()
+        .find(|&direction| from.move_to(direction) == Some(*to))
+        .unwrap_or_else(|| panic!("{} and {} are not adjacent", from, to))
+}
+
+// passable cells contracted to the junctions (cells without exactly two passable
+// neighbors, plus any special points) and the one-wide corridors between them
+#[derive(Clone, Debug)]
+pub struct JunctionGraph {
+    junctions: Vec<Point<usize>>,
+    indices: HashMap<Point<usize>, usize>,
+    corridors: Vec<Corridor>,
+    outgoing: Vec<Vec<usize>>,
+}
+
+#[allow(dead_code)]
+impl JunctionGraph {
+    pub fn len(&self) -> usize {
+        self.junctions.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.junctions.is_empty()
+    }
+    pub fn junction(&self, index: usize) -> &Point<usize> {
+        &self.junctions[index]
+    }
+    pub fn junctions(&self) -> &[Point<usize>] {
+        &self.junctions
+    }
+    pub fn index_of(&self, point: &Point<usize>) -> Option<usize> {
+        self.indices.get(point).copied()
+    }
+    pub fn corridor(&self, id: usize) -> &Corridor {
+        &self.corridors[id]
+    }
+    pub fn corridor_count(&self) -> usize {
+        self.corridors.len()
+    }
+    pub fn corridors_from(&self, junction: usize) -> impl Iterator<Item = (usize, &Corridor)> {
+        self.outgoing[junction]
+            .iter()
+            .map(|&id| (id, &self.corridors[id]))
+    }
+    // maps a chain of corridor ids back to the cells walked, without repeating junctions
+    pub fn expand(&self, corridors: impl IntoIterator<Item = usize>) -> Vec<Point<usize>> {
+        let mut cells: Vec<Point<usize>> = Vec::new();
+        for id in corridors {
+            let corridor = &self.corridors[id];
+            let skip = usize::from(cells.last() == corridor.cells.first());
+            cells.extend(&corridor.cells[skip..]);
+        }
+        cells
+    }
+}
+
+#[allow(dead_code)]
+impl<T> Grid<T> {
+    pub fn junction_graph(
+        &self,
+        passable: impl FnMut(&Point<usize>, &T) -> bool,
+        special: impl IntoIterator<Item = Point<usize>>,
+    ) -> JunctionGraph {
+        let open = &self.to_bit_grid(passable);
+        let neighbors = |point: &Point<usize>| {
+            let point = *point;
+            Direction::directions()
+                .into_iter()
+                .filter_map(move |direction| {
+                    point
+                        .move_to(direction)
+                        .filter(|next| open.contains(next))
+                        .map(|next| (direction, next))
+                })
+        };
+
+        let mut junctions: Vec<Point<usize>> = open
+            .iter()
+            .filter(|point| neighbors(point).count() != 2)
+            .collect();
+        for point in special {
+            if !junctions.contains(&point) {
+                junctions.push(point);
+            }
+        }
+        let indices: HashMap<_, _> = junctions
+            .iter()
+            .enumerate()
+            .map(|(index, &point)| (point, index))
+            .collect();
+
+        let mut corridors = Vec::new();
+        let mut outgoing = vec![Vec::new(); junctions.len()];
+        for (from, start) in junctions.iter().enumerate() {
+            for (start_direction, next) in neighbors(start) {
+                let mut cells = vec![*start, next];
+                let mut direction = start_direction;
+                let mut turns = 0;
+                while !indices.contains_key(cells.last().unwrap()) {
+                    let current = *cells.last().unwrap();
+                    let (next_direction, next) = neighbors(&current)
+                        .find(|&(d, _)| d != direction.opposite())
+                        .unwrap();
+                    if next_direction != direction {
+                        turns += 1;
+                    }
+                    direction = next_direction;
+                    cells.push(next);
+                }
+                outgoing[from].push(corridors.len());
+                corridors.push(Corridor {
+                    from,
+                    to: indices[cells.last().unwrap()],
+                    length: cells.len() - 1,
+                    turns,
+                    start_direction,
+                    end_direction: direction,
+                    cells,
+                });
+            }
+        }
+
+        JunctionGraph {
+            junctions,
+            indices,
+            corridors,
+            outgoing,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(input: &str, special: &[Point<usize>]) -> JunctionGraph {
+        input
+            .parse::<Grid<char>>()
+            .unwrap()
+            .junction_graph(|_, &c| c != '#', special.iter().copied())
+    }
+
+    #[test]
+    fn test_corridors() {
+        let graph = graph("#######\n#.....#\n#.###.#\n#...#.#\n###.#.#\n#######", &[]);
+        // two dead ends at (3, 4) and (5, 4), one corridor winding between them
+        assert_eq!(graph.junctions(), &[Point::new(3, 4), Point::new(5, 4)]);
+        assert_eq!(graph.corridor_count(), 2);
+        let (_, corridor) = graph.corridors_from(0).next().unwrap();
+        assert_eq!(corridor.to, 1);
+        assert_eq!(corridor.length, 12);
+        assert_eq!(corridor.turns, 4);
+        assert_eq!(corridor.start_direction, Direction::Up);
+        assert_eq!(corridor.end_direction, Direction::Down);
+        assert_eq!(corridor.steps().count(), 12);
+    }
+
+    #[test]
+    fn test_special_points_and_expand() {
+        let start = Point::new(1, 1);
+        let middle = Point::new(3, 1);
+        let graph = graph("#######\n#.....#\n###.###\n#######", &[start, middle]);
+        let start = graph.index_of(&start).unwrap();
+        let middle = graph.index_of(&middle).unwrap();
+        let to_middle = graph
+            .corridors_from(start)
+            .find(|(_, c)| c.to == middle)
+            .unwrap()
+            .0;
+        let (to_end, end) = graph
+            .corridors_from(middle)
+            .find(|(_, c)| c.end_direction == Direction::Right)
+            .map(|(id, c)| (id, c.to))
+            .unwrap();
+        assert_eq!(graph.junction(end), &Point::new(5, 1));
+        assert_eq!(
+            graph.expand([to_middle, to_end]),
+            (1..=5).map(|x| Point::new(x, 1)).collect::<Vec<_>>()
+        );
+        assert_eq!(graph.corridors_from(middle).count(), 3);
+    }
+}
@@ -0,0 +1,149 @@
+use crate::common::models::Point;
+
+pub fn gcd(a: i64, b: i64) -> i64 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+#[allow(dead_code)]
+impl Point<i64> {
+    // the smallest lattice step with the same direction, zero stays zero
+    pub fn reduced(&self) -> Self {
+        match gcd(self.x, self.y) {
+            0 => *self,
+            divisor => Point::new(self.x / divisor, self.y / divisor),
+        }
+    }
+    // self + (other - self) * numerator / denominator, if that lands on a lattice point;
+    // ratio 0 is self, 1 is other, 2 is as far beyond other as other is from self
+    pub fn at_ratio(&self, other: &Self, numerator: i64, denominator: i64) -> Option<Self> {
+        assert_ne!(denominator, 0, "ratio denominator cannot be zero");
+        let diff = *other - *self;
+        let (x, y) = (diff.x * numerator, diff.y * numerator);
+        (x % denominator == 0 && y % denominator == 0)
+            .then(|| *self + Point::new(x / denominator, y / denominator))
+    }
+}
+
+#[allow(dead_code)]
+pub fn ray(start: Point<i64>, step: Point<i64>) -> impl Iterator<Item = Point<i64>> {
+    std::iter::successors(Some(start), move |&point| Some(point + step))
+}
+
+// every lattice point on the infinite line through two distinct points is origin + step * k
+#[derive(Copy, Clone, Hash, PartialEq, Eq, Debug)]
+pub struct Line {
+    origin: Point<i64>,
+    step: Point<i64>,
+}
+
+#[allow(dead_code)]
+impl Line {
+    pub fn through(a: &Point<i64>, b: &Point<i64>) -> Self {
+        assert_ne!(a, b, "a line needs two distinct points");
+        Self {
+            origin: *a,
+            step: (*b - *a).reduced(),
+        }
+    }
+    pub fn origin(&self) -> &Point<i64> {
+        &self.origin
+    }
+    pub fn step(&self) -> &Point<i64> {
+        &self.step
+    }
+    pub fn point_at(&self, k: i64) -> Point<i64> {
+        self.origin + self.step * k
+    }
+    pub fn contains(&self, point: &Point<i64>) -> bool {
+        let diff = *point - self.origin;
+        diff.x * self.step.y == diff.y * self.step.x
+    }
+    // the lattice points inside the inclusive rectangle from min to max, ordered along the step
+    pub fn points_within(
+        &self,
+        min: &Point<i64>,
+        max: &Point<i64>,
+    ) -> impl Iterator<Item = Point<i64>> + '_ {
+        let (x_from, x_to) = steps_within(self.origin.x, self.step.x, min.x, max.x);
+        let (y_from, y_to) = steps_within(self.origin.y, self.step.y, min.y, max.y);
+        (x_from.max(y_from)..=x_to.min(y_to)).map(|k| self.point_at(k))
+    }
+}
+
+// the range of k with min <= origin + step * k <= max, empty when from > to
+fn steps_within(origin: i64, step: i64, min: i64, max: i64) -> (i64, i64) {
+    match step {
+        0 if (min..=max).contains(&origin) => (i64::MIN, i64::MAX),
+        0 => (1, 0),
+        step if step < 0 => steps_within(-origin, -step, -max, -min),
+        step => (
+            -(origin - min).div_euclid(step),
+            (max - origin).div_euclid(step),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reduced_and_ratios() {
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(Point::new(4, -6).reduced(), Point::new(2, -3));
+        assert_eq!(Point::new(0, -5).reduced(), Point::new(0, -1));
+        assert_eq!(Point::new(0, 0).reduced(), Point::new(0, 0));
+
+        let a = Point::new(4, 3);
+        let b = Point::new(5, 5);
+        assert_eq!(a.at_ratio(&b, 2, 1), Some(Point::new(6, 7)));
+        assert_eq!(a.at_ratio(&b, -1, 1), Some(Point::new(3, 1)));
+        assert_eq!(a.at_ratio(&b, 1, 2), None);
+        let c = Point::new(7, 9);
+        assert_eq!(a.at_ratio(&c, 1, 3), Some(Point::new(5, 5)));
+    }
+
+    #[test]
+    fn test_line_points() {
+        let line = Line::through(&Point::new(2, 2), &Point::new(6, 4));
+        assert_eq!(line.step(), &Point::new(2, 1));
+        assert!(line.contains(&Point::new(4, 3)));
+        assert!(line.contains(&Point::new(-2, 0)));
+        assert!(!line.contains(&Point::new(3, 3)));
+        assert_eq!(line.point_at(-1), Point::new(0, 1));
+
+        let (min, max) = (Point::new(0, 0), Point::new(7, 4));
+        assert_eq!(
+            line.points_within(&min, &max).collect::<Vec<_>>(),
+            vec![
+                Point::new(0, 1),
+                Point::new(2, 2),
+                Point::new(4, 3),
+                Point::new(6, 4)
+            ]
+        );
+        // the origin does not have to be inside the bounds
+        let outside = Line::through(&Point::new(9, 0), &Point::new(10, 0));
+        assert_eq!(
+            outside.points_within(&min, &max).collect::<Vec<_>>(),
+            (0..8).map(|x| Point::new(x, 0)).collect::<Vec<_>>()
+        );
+        let backwards = Line::through(&Point::new(10, -3), &Point::new(7, -1));
+        assert_eq!(
+            backwards.points_within(&min, &max).collect::<Vec<_>>(),
+            vec![Point::new(4, 1), Point::new(1, 3)]
+        );
+        let missing = Line::through(&Point::new(0, 6), &Point::new(1, 6));
+        assert_eq!(missing.points_within(&min, &max).count(), 0);
+        assert_eq!(
+            ray(Point::new(0, 0), Point::new(1, -1))
+                .take(3)
+                .collect::<Vec<_>>(),
+            vec![Point::new(0, 0), Point::new(1, -1), Point::new(2, -2)]
+        );
+    }
+}
@@ -1,6 +1,28 @@
 ﻿use std::borrow::Cow;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::time::Duration;
+pub mod bit_grid;
+pub mod components;
+pub mod diff;
+pub mod distances;
+pub mod dsu;
+pub mod geometry;
+pub mod grid_cell;
+pub mod image;
+pub mod junctions;
+pub mod line_of_sight;
 pub mod models;
 pub mod linear_algebra;
+pub mod parallel;
+pub mod parse;
+pub mod parse_error;
+pub mod pattern;
+pub mod playback;
+pub mod search;
+pub mod snapshot;
+pub mod sparse_grid;
+pub mod styled;
 
 pub type InputProvider = dyn Fn() -> TextInput;
 #[derive(Default)]
@@ -8,6 +30,10 @@ pub struct Context {
     testing: Option<usize>,
     text_input: Option<Box<InputProvider>>,
     testing_inputs: Vec<Box<InputProvider>>,
+    part_1_answer: Option<String>,
+    part_2_answer: Option<String>,
+    image_dir: Option<PathBuf>,
+    playback_delay: Option<Duration>,
 }
 
 impl Context {
@@ -37,6 +63,36 @@ impl Context {
         }
         panic!("No input provider set");
     }
+
+    pub fn set_part_1_answer(&mut self, answer: impl Display) {
+        self.part_1_answer = Some(answer.to_string());
+    }
+    pub fn set_part_2_answer(&mut self, answer: impl Display) {
+        self.part_2_answer = Some(answer.to_string());
+    }
+    pub fn answers(&self) -> (Option<&str>, Option<&str>) {
+        (self.part_1_answer.as_deref(), self.part_2_answer.as_deref())
+    }
+
+    pub fn set_image_dir(&mut self, image_dir: impl Into<PathBuf>) {
+        self.image_dir = Some(image_dir.into());
+    }
+    pub fn save_image(&self, name: &str, image: impl FnOnce() -> image::Image) {
+        let Some(image_dir) = &self.image_dir else {
+            return;
+        };
+        let path = image_dir.join(name);
+        match image().save(&path) {
+            Ok(()) => log::info!("saved {}", path.display()),
+            Err(e) => log::warn!("{:#}", e),
+        }
+    }
+    pub fn set_playback_delay(&mut self, delay: Duration) {
+        self.playback_delay = Some(delay);
+    }
+    pub fn playback(&self) -> Option<playback::Playback> {
+        self.playback_delay.map(playback::Playback::new)
+    }
 }
 
 pub struct TextInput(Cow<'static, str>);
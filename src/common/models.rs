@@ -1,10 +1,11 @@
-﻿use anyhow::Context;
+﻿use crate::common::parse_error::{parse_str, ParseError};
 use std::collections::HashMap;
 use std::fmt::{Debug, Display, Formatter};
 use std::iter::Sum;
-use std::ops::{Add, Sub};
+use std::num::TryFromIntError;
+use std::ops::{Add, AddAssign, Index, IndexMut, Sub, SubAssign};
 use std::ops::{Div, Mul, Neg};
-use utils::a_star::Node;
+use std::str::FromStr;
 
 pub trait Numeric:
     Add<Output = Self>
@@ -38,7 +39,7 @@ impl<T> Numeric for T where
 pub trait NumericNeg: Numeric + Neg<Output = Self> {}
 impl<T> NumericNeg for T where T: Numeric + Neg<Output = Self> {}
 
-#[derive(Clone, Default, Hash, PartialEq, Eq, Ord, PartialOrd, Debug)]
+#[derive(Copy, Clone, Default, Hash, PartialEq, Eq, Ord, PartialOrd, Debug)]
 pub struct Point<T: Numeric> {
     pub x: T,
     pub y: T,
@@ -50,51 +51,68 @@ impl<T: Numeric> Point<T> {
     }
 }
 
-impl Point<usize> {
-    pub fn manhattan_distance(&self, other: &Self) -> usize {
-        let x = if self.x > other.x {
-            self.x - other.x
-        } else {
-            other.x - self.x
-        };
-        let y = if self.y > other.y {
-            self.y - other.y
-        } else {
-            other.y - self.y
-        };
-        x + y
+#[allow(dead_code)]
+impl<T: Numeric> Point<T> {
+    pub fn manhattan_distance(&self, other: &Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
     }
-    pub fn move_to(&self, direction: Direction) -> Option<Self> {
-        match direction {
-            Direction::Up => {
-                if self.y > 0 {
-                    Some(Self {
-                        x: self.x,
-                        y: self.y - 1,
-                    })
-                } else {
-                    None
-                }
-            }
-            Direction::Down => Some(Self {
-                x: self.x,
-                y: self.y + 1,
-            }),
-            Direction::Left => {
-                if self.x > 0 {
-                    Some(Self {
-                        x: self.x - 1,
-                        y: self.y,
-                    })
-                } else {
-                    None
-                }
-            }
-            Direction::Right => Some(Self {
-                x: self.x + 1,
-                y: self.y,
-            }),
-        }
+    pub fn chebyshev_distance(&self, other: &Self) -> T {
+        max(abs_diff(self.x, other.x), abs_diff(self.y, other.y))
+    }
+}
+
+fn abs_diff<T: Numeric>(left: T, right: T) -> T {
+    if left > right {
+        left - right
+    } else {
+        right - left
+    }
+}
+
+fn max<T: Numeric>(left: T, right: T) -> T {
+    if left > right {
+        left
+    } else {
+        right
+    }
+}
+
+#[allow(dead_code)]
+impl Point<i64> {
+    pub fn move_to(&self, direction: impl Into<CompassDirection>) -> Self {
+        *self + direction.into().offset()
+    }
+}
+
+impl TryFrom<Point<i64>> for Point<usize> {
+    type Error = TryFromIntError;
+
+    fn try_from(value: Point<i64>) -> Result<Self, Self::Error> {
+        Ok(Point {
+            x: value.x.try_into()?,
+            y: value.y.try_into()?,
+        })
+    }
+}
+
+impl TryFrom<Point<usize>> for Point<i64> {
+    type Error = TryFromIntError;
+
+    fn try_from(value: Point<usize>) -> Result<Self, Self::Error> {
+        Ok(Point {
+            x: value.x.try_into()?,
+            y: value.y.try_into()?,
+        })
+    }
+}
+
+impl Point<usize> {
+    pub fn move_to(&self, direction: impl Into<CompassDirection>) -> Option<Self> {
+        let offset = direction.into().offset();
+        Some(Self {
+            x: self.x.checked_add_signed(offset.x as isize)?,
+            y: self.y.checked_add_signed(offset.y as isize)?,
+        })
     }
     pub fn try_sub(&self, rhs: Self) -> Option<Self> {
         if rhs.x > self.x || rhs.y > self.y {
@@ -108,13 +126,32 @@ impl Point<usize> {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct Grid<T> {
-    map: Box<[Box<[T]>]>,
+    map: Box<[T]>,
     size_x: usize,
     size_y: usize,
 }
 
+impl<T: Clone> Clone for Grid<T> {
+    fn clone(&self) -> Self {
+        Self {
+            map: self.map.clone(),
+            size_x: self.size_x,
+            size_y: self.size_y,
+        }
+    }
+    fn clone_from(&mut self, source: &Self) {
+        if self.map.len() == source.map.len() {
+            self.map.clone_from_slice(&source.map);
+        } else {
+            self.map = source.map.clone();
+        }
+        self.size_x = source.size_x;
+        self.size_y = source.size_y;
+    }
+}
+
 impl<T> Grid<T> {
     #[allow(dead_code)]
     pub fn new(size_x: usize, size_y: usize) -> Self
@@ -122,16 +159,27 @@ impl<T> Grid<T> {
         T: Default,
     {
         Self {
-            map: (0..size_y)
-                .map(|_| (0..size_x).map(|_| T::default()).collect())
-                .collect(),
+            map: (0..size_x * size_y).map(|_| T::default()).collect(),
             size_x,
             size_y,
         }
     }
-    pub fn iter(&self) -> GridIterator<T> {
-        GridIterator::new(self)
+    pub fn iter(&self) -> GridIterator<'_, T> {
+        GridCells::new(self.size_x, self.map.iter())
+    }
+    #[allow(dead_code)]
+    pub fn iter_mut(&mut self) -> GridIterMut<'_, T> {
+        GridCells::new(self.size_x, self.map.iter_mut())
+    }
+    #[allow(dead_code)]
+    pub fn map<U>(&self, mut f: impl FnMut(&Point<usize>, &T) -> U) -> Grid<U> {
+        Grid {
+            map: self.iter().map(|(point, value)| f(&point, value)).collect(),
+            size_x: self.size_x,
+            size_y: self.size_y,
+        }
     }
+    #[allow(dead_code)]
     pub fn eight_way_neighbors(&self, point: &Point<usize>) -> Vec<Point<usize>> {
         let mut neighbors = Vec::new();
         for x in point.x.saturating_sub(1)..(point.x + 2).min(self.size_x) {
@@ -171,6 +219,39 @@ impl<T> Grid<T> {
         }
         neighbors
     }
+    #[allow(dead_code)]
+    pub fn compass_neighbors(
+        &self,
+        point: &Point<usize>,
+    ) -> impl Iterator<Item = (CompassDirection, Point<usize>)> + '_ {
+        let point = *point;
+        CompassDirection::directions()
+            .into_iter()
+            .filter_map(move |direction| {
+                point
+                    .move_to(direction)
+                    .filter(|next| self.get(next).is_some())
+                    .map(|next| (direction, next))
+            })
+    }
+    // starts at `start` itself and stops at the first step outside the grid
+    #[allow(dead_code)]
+    pub fn ray(
+        &self,
+        start: &Point<usize>,
+        direction: impl Into<CompassDirection>,
+    ) -> impl Iterator<Item = Point<usize>> + '_ {
+        let direction = direction.into();
+        std::iter::successors(self.get(start).map(|_| *start), move |point| {
+            point
+                .move_to(direction)
+                .filter(|next| self.get(next).is_some())
+        })
+    }
+    #[allow(dead_code)]
+    pub fn torus(&self) -> Torus {
+        Torus::new(self.size_x, self.size_y)
+    }
     pub fn try_move_if(
         &self,
         point: &Point<usize>,
@@ -192,19 +273,23 @@ impl<T> Grid<T> {
         I: Iterator,
         I::Item: IntoIterator<Item = T>,
     {
-        let map: Box<[Box<[T]>]> = iter
-            .into_iter()
-            .map(|row| row.into_iter().collect())
-            .collect();
-        let size_y = map.len();
-        let size_x = map.first().map_or(0, |row| row.len());
-        assert!(
-            map.iter().all(|row| row.len() == size_x),
-            "All rows must be the same length"
-        );
+        let mut map = Vec::new();
+        let mut size_x = None;
+        let mut size_y = 0;
+        for row in iter {
+            let start = map.len();
+            map.extend(row);
+            let len = map.len() - start;
+            assert_eq!(
+                *size_x.get_or_insert(len),
+                len,
+                "All rows must be the same length"
+            );
+            size_y += 1;
+        }
         Self {
-            map,
-            size_x,
+            map: map.into_boxed_slice(),
+            size_x: size_x.unwrap_or(0),
             size_y,
         }
     }
@@ -213,19 +298,25 @@ impl<T> Grid<T> {
         I: Iterator,
         I::Item: IntoIterator<Item = Result<T, E>>,
     {
-        let map: Box<[Box<[T]>]> = iter
-            .into_iter()
-            .map(|row| row.into_iter().collect::<Result<_, E>>())
-            .collect::<Result<_, E>>()?;
-        let size_y = map.len();
-        let size_x = map.first().map_or(0, |row| row.len());
-        assert!(
-            map.iter().all(|row| row.len() == size_x),
-            "All rows must be the same length"
-        );
+        let mut map = Vec::new();
+        let mut size_x = None;
+        let mut size_y = 0;
+        for row in iter {
+            let start = map.len();
+            for item in row {
+                map.push(item?);
+            }
+            let len = map.len() - start;
+            assert_eq!(
+                *size_x.get_or_insert(len),
+                len,
+                "All rows must be the same length"
+            );
+            size_y += 1;
+        }
         Ok(Self {
-            map,
-            size_x,
+            map: map.into_boxed_slice(),
+            size_x: size_x.unwrap_or(0),
             size_y,
         })
     }
@@ -237,7 +328,7 @@ impl<T> Grid<T> {
     }
     pub fn get(&self, point: &Point<usize>) -> Option<&T> {
         if point.x < self.size_x && point.y < self.size_y {
-            Some(&self.map[point.y][point.x])
+            Some(&self.map[point.y * self.size_x + point.x])
         } else {
             None
         }
@@ -245,26 +336,174 @@ impl<T> Grid<T> {
     #[allow(dead_code)]
     pub fn get_mut(&mut self, point: &Point<usize>) -> Option<&mut T> {
         if point.x < self.size_x && point.y < self.size_y {
-            Some(&mut self.map[point.y][point.x])
+            Some(&mut self.map[point.y * self.size_x + point.x])
         } else {
             None
         }
     }
     pub fn set(&mut self, point: &Point<usize>, value: T) {
-        self.map[point.y][point.x] = value;
+        self[point] = value;
+    }
+    #[allow(dead_code)]
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.map[y * self.size_x..(y + 1) * self.size_x]
+    }
+    #[allow(dead_code)]
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.map[y * self.size_x..(y + 1) * self.size_x]
+    }
+    #[allow(dead_code)]
+    pub fn as_slice(&self) -> &[T] {
+        &self.map
+    }
+    #[allow(dead_code)]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.map
+    }
+    #[allow(dead_code)]
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.size_y).map(|y| self.row(y))
+    }
+    fn index_of(&self, point: &Point<usize>) -> usize {
+        assert!(
+            point.x < self.size_x && point.y < self.size_y,
+            "{} is outside of a {}x{} grid",
+            point,
+            self.size_x,
+            self.size_y
+        );
+        point.y * self.size_x + point.x
+    }
+}
+
+impl<T> Index<&Point<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: &Point<usize>) -> &Self::Output {
+        &self.map[self.index_of(point)]
+    }
+}
+
+impl<T> IndexMut<&Point<usize>> for Grid<T> {
+    fn index_mut(&mut self, point: &Point<usize>) -> &mut Self::Output {
+        let index = self.index_of(point);
+        &mut self.map[index]
+    }
+}
+
+#[allow(dead_code)]
+impl<T> Grid<T> {
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.size_x, "column {} is outside of the grid", x);
+        self.map.iter().skip(x).step_by(self.size_x)
+    }
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.size_x).map(|x| self.column(x))
+    }
+    pub fn view(
+        &self,
+        origin: &Point<usize>,
+        len_x: usize,
+        len_y: usize,
+    ) -> Option<GridView<'_, T>> {
+        if origin.x + len_x <= self.size_x && origin.y + len_y <= self.size_y {
+            Some(GridView {
+                grid: self,
+                origin: *origin,
+                len_x,
+                len_y,
+            })
+        } else {
+            None
+        }
+    }
+    pub fn views(&self, len_x: usize, len_y: usize) -> impl Iterator<Item = GridView<'_, T>> {
+        let origins_x = (self.size_x + 1).saturating_sub(len_x);
+        let origins_y = (self.size_y + 1).saturating_sub(len_y);
+        (0..origins_y).flat_map(move |y| {
+            (0..origins_x).map(move |x| self.view(&Point { x, y }, len_x, len_y).unwrap())
+        })
+    }
+}
+
+#[allow(dead_code)]
+impl<T: Clone> Grid<T> {
+    pub fn rotated_90(&self) -> Self {
+        self.remap(self.size_y, self.size_x, |p| Point {
+            x: p.y,
+            y: self.size_y - 1 - p.x,
+        })
+    }
+    pub fn rotated_180(&self) -> Self {
+        self.remap(self.size_x, self.size_y, |p| Point {
+            x: self.size_x - 1 - p.x,
+            y: self.size_y - 1 - p.y,
+        })
+    }
+    pub fn rotated_270(&self) -> Self {
+        self.remap(self.size_y, self.size_x, |p| Point {
+            x: self.size_x - 1 - p.y,
+            y: p.x,
+        })
+    }
+    pub fn rotations(&self) -> [Self; 4] {
+        [
+            self.clone(),
+            self.rotated_90(),
+            self.rotated_180(),
+            self.rotated_270(),
+        ]
+    }
+    pub fn flipped_horizontal(&self) -> Self {
+        self.remap(self.size_x, self.size_y, |p| Point {
+            x: self.size_x - 1 - p.x,
+            y: p.y,
+        })
+    }
+    pub fn flipped_vertical(&self) -> Self {
+        self.remap(self.size_x, self.size_y, |p| Point {
+            x: p.x,
+            y: self.size_y - 1 - p.y,
+        })
+    }
+    pub fn transposed(&self) -> Self {
+        self.remap(self.size_y, self.size_x, |p| Point { x: p.y, y: p.x })
+    }
+    fn remap(
+        &self,
+        len_x: usize,
+        len_y: usize,
+        source: impl Fn(Point<usize>) -> Point<usize>,
+    ) -> Self {
+        Self::from_iter((0..len_y).map(|y| {
+            let source = &source;
+            (0..len_x).map(move |x| self[&source(Point { x, y })].clone())
+        }))
     }
 }
 
 impl<T: Default> Grid<T> {
-    pub fn from_str_with<F: FnMut(char, &Point<usize>) -> anyhow::Result<Option<T>>>(
+    pub fn from_str_with<F: FnMut(char, &Point<usize>) -> Result<Option<T>, ParseError>>(
         s: &str,
         mut parse_item: F,
-    ) -> Result<Self, anyhow::Error> {
+    ) -> Result<Self, ParseError> {
         let size_y = s.lines().count();
-        let size_x = s.lines().next().context("empty game map")?.chars().count();
+        let size_x = s
+            .lines()
+            .next()
+            .ok_or_else(|| ParseError::missing(s, "a grid"))?
+            .chars()
+            .count();
         let mut grid = Self::new(size_x, size_y);
 
         for (y, line) in s.lines().enumerate() {
+            if line.chars().count() != size_x {
+                return Err(ParseError::at_point(
+                    s,
+                    &Point { x: 0, y },
+                    format!("expected a row of length {}", size_x),
+                ));
+            }
             for (x, c) in line.chars().enumerate() {
                 let point = Point { x, y };
                 if let Some(item) = parse_item(c, &point)? {
@@ -277,51 +516,242 @@ impl<T: Default> Grid<T> {
     }
 }
 
-pub struct GridIterator<'a, T> {
-    grid: &'a Grid<T>,
-    x: usize,
-    y: usize,
+pub type GridIterator<'a, T> = GridCells<std::slice::Iter<'a, T>>;
+pub type GridIterMut<'a, T> = GridCells<std::slice::IterMut<'a, T>>;
+pub type GridIntoIter<T> = GridCells<std::vec::IntoIter<T>>;
+
+// pairs every cell of the row-major storage with its point
+pub struct GridCells<I> {
+    size_x: usize,
+    cells: std::iter::Enumerate<I>,
 }
 
-impl<'a, T> GridIterator<'a, T> {
-    fn new(grid: &'a Grid<T>) -> Self {
-        Self { grid, x: 0, y: 0 }
-    }
-    fn next_point(&mut self) {
-        if self.x < self.grid.len_x() - 1 {
-            self.x += 1;
-        } else if self.y < self.grid.len_y() - 1 {
-            self.x = 0;
-            self.y += 1;
-        } else {
-            self.x = self.grid.len_x();
-            self.y = self.grid.len_y();
+impl<I: Iterator> GridCells<I> {
+    fn new(size_x: usize, cells: I) -> Self {
+        Self {
+            size_x,
+            cells: cells.enumerate(),
         }
     }
+    fn with_point(&self, (index, value): (usize, I::Item)) -> (Point<usize>, I::Item) {
+        let point = Point {
+            x: index % self.size_x,
+            y: index / self.size_x,
+        };
+        (point, value)
+    }
 }
 
-impl<'a, T> Iterator for GridIterator<'a, T> {
-    type Item = (Point<usize>, &'a T);
+impl<I: Iterator> Iterator for GridCells<I> {
+    type Item = (Point<usize>, I::Item);
 
     fn next(&mut self) -> Option<Self::Item> {
-        let point = Point {
-            x: self.x,
-            y: self.y,
-        };
-        if self.x < self.grid.len_x() && self.y < self.grid.len_y() {
-            self.next_point();
-            let value = self.grid.get(&point).unwrap();
-            Some((point, value))
+        let cell = self.cells.next()?;
+        Some(self.with_point(cell))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cells.size_hint()
+    }
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let cell = self.cells.nth(n)?;
+        Some(self.with_point(cell))
+    }
+}
+
+impl<I: DoubleEndedIterator + ExactSizeIterator> DoubleEndedIterator for GridCells<I> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let cell = self.cells.next_back()?;
+        Some(self.with_point(cell))
+    }
+}
+
+impl<I: ExactSizeIterator> ExactSizeIterator for GridCells<I> {}
+
+impl<I: std::iter::FusedIterator> std::iter::FusedIterator for GridCells<I> {}
+
+impl<T> IntoIterator for Grid<T> {
+    type Item = (Point<usize>, T);
+    type IntoIter = GridIntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        GridCells::new(self.size_x, self.map.into_vec().into_iter())
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Grid<T> {
+    type Item = (Point<usize>, &'a T);
+    type IntoIter = GridIterator<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut Grid<T> {
+    type Item = (Point<usize>, &'a mut T);
+    type IntoIter = GridIterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    origin: Point<usize>,
+    len_x: usize,
+    len_y: usize,
+}
+
+#[allow(dead_code)]
+impl<'a, T> GridView<'a, T> {
+    pub fn origin(&self) -> &Point<usize> {
+        &self.origin
+    }
+    pub fn len_x(&self) -> usize {
+        self.len_x
+    }
+    pub fn len_y(&self) -> usize {
+        self.len_y
+    }
+    pub fn get(&self, point: &Point<usize>) -> Option<&'a T> {
+        if point.x < self.len_x && point.y < self.len_y {
+            self.grid.get(&(&self.origin + point))
         } else {
             None
         }
     }
-
-    fn count(self) -> usize
+    pub fn row(&self, y: usize) -> &'a [T] {
+        assert!(y < self.len_y, "row {} is outside of the view", y);
+        &self.grid.row(self.origin.y + y)[self.origin.x..self.origin.x + self.len_x]
+    }
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> + '_ {
+        (0..self.len_y).map(|y| self.row(y))
+    }
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &'a T> + '_ {
+        assert!(x < self.len_x, "column {} is outside of the view", x);
+        self.rows().map(move |row| &row[x])
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (Point<usize>, &'a T)> + '_ {
+        self.rows().enumerate().flat_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(x, value)| (Point { x, y }, value))
+        })
+    }
+    pub fn to_grid(&self) -> Grid<T>
     where
-        Self: Sized,
+        T: Clone,
     {
-        self.grid.len_x() * self.grid.len_y()
+        Grid::from_iter(self.rows().map(|row| row.iter().cloned()))
+    }
+}
+
+#[derive(Copy, Clone, Hash, PartialEq, Eq, Debug)]
+pub struct Torus {
+    len_x: usize,
+    len_y: usize,
+}
+
+#[allow(dead_code)]
+impl Torus {
+    pub fn new(len_x: usize, len_y: usize) -> Self {
+        assert!(len_x > 0 && len_y > 0, "a torus cannot be empty");
+        Self { len_x, len_y }
+    }
+    pub fn len_x(&self) -> usize {
+        self.len_x
+    }
+    pub fn len_y(&self) -> usize {
+        self.len_y
+    }
+    pub fn wrap(&self, point: &Point<i64>) -> Point<usize> {
+        Point {
+            x: point.x.rem_euclid(self.len_x as i64) as usize,
+            y: point.y.rem_euclid(self.len_y as i64) as usize,
+        }
+    }
+    pub fn step(&self, point: &Point<usize>, velocity: &Point<i64>, times: i64) -> Point<usize> {
+        let x = (velocity.x % self.len_x as i64) * (times % self.len_x as i64);
+        let y = (velocity.y % self.len_y as i64) * (times % self.len_y as i64);
+        self.wrap(&Point {
+            x: point.x as i64 + x,
+            y: point.y as i64 + y,
+        })
+    }
+    pub fn move_to(&self, point: &Point<usize>, direction: Direction) -> Point<usize> {
+        self.step(point, &direction.offset(), 1)
+    }
+    pub fn neighbors(&self, point: &Point<usize>) -> [Point<usize>; 4] {
+        Direction::directions().map(|direction| self.move_to(point, direction))
+    }
+    pub fn partition(
+        &self,
+        point: &Point<usize>,
+        parts_x: usize,
+        parts_y: usize,
+    ) -> Option<Point<usize>> {
+        Some(Point {
+            x: partition_index(point.x, self.len_x, parts_x)?,
+            y: partition_index(point.y, self.len_y, parts_y)?,
+        })
+    }
+    pub fn quadrant(&self, point: &Point<usize>) -> Option<Quadrant> {
+        self.partition(point, 2, 2)
+            .map(|part| match (part.x, part.y) {
+                (0, 0) => Quadrant::NorthWest,
+                (1, 0) => Quadrant::NorthEast,
+                (0, 1) => Quadrant::SouthWest,
+                _ => Quadrant::SouthEast,
+            })
+    }
+    pub fn quadrant_counts<'a>(
+        &self,
+        points: impl IntoIterator<Item = (&'a Point<usize>, usize)>,
+    ) -> [usize; 4] {
+        let mut counts = [0; 4];
+        for (point, count) in points {
+            if let Some(quadrant) = self.quadrant(point) {
+                counts[quadrant as usize] += count;
+            }
+        }
+        counts
+    }
+}
+
+// splits `len` cells into `parts` equal ranges; when one cell is left over between each pair
+// of ranges (like the middle row of an odd-sized board) those dividing cells belong to none
+fn partition_index(coordinate: usize, len: usize, parts: usize) -> Option<usize> {
+    if len.is_multiple_of(parts) {
+        Some(coordinate / (len / parts))
+    } else if len % parts == parts - 1 {
+        let size = len / parts;
+        if coordinate % (size + 1) == size {
+            None
+        } else {
+            Some(coordinate / (size + 1))
+        }
+    } else {
+        panic!("{} cells cannot be split into {} equal parts", len, parts)
+    }
+}
+
+#[derive(Copy, Clone, Hash, PartialEq, Eq, Debug)]
+pub enum Quadrant {
+    NorthWest,
+    NorthEast,
+    SouthWest,
+    SouthEast,
+}
+
+impl Quadrant {
+    pub fn quadrants() -> [Quadrant; 4] {
+        [
+            Quadrant::NorthWest,
+            Quadrant::NorthEast,
+            Quadrant::SouthWest,
+            Quadrant::SouthEast,
+        ]
     }
 }
 
@@ -337,6 +767,14 @@ impl Direction {
     pub fn directions() -> [Direction; 4] {
         [Direction::Up, Direction::Down, Direction::Left, Direction::Right]
     }
+    pub fn offset(&self) -> Point<i64> {
+        match self {
+            Direction::Up => Point::new(0, -1),
+            Direction::Down => Point::new(0, 1),
+            Direction::Left => Point::new(-1, 0),
+            Direction::Right => Point::new(1, 0),
+        }
+    }
     pub fn turn_right(&self) -> Self {
         match self {
             Direction::Up => Direction::Right,
@@ -353,6 +791,119 @@ impl Direction {
             Direction::Right => Direction::Up,
         }
     }
+    #[allow(dead_code)]
+    pub fn opposite(&self) -> Self {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+    #[allow(dead_code)]
+    pub fn quarter_turns_to(&self, other: &Self) -> usize {
+        if self == other {
+            0
+        } else if self.opposite() == *other {
+            2
+        } else {
+            1
+        }
+    }
+}
+
+#[derive(Copy, Clone, Hash, PartialEq, Eq, Ord, PartialOrd, Debug)]
+pub enum CompassDirection {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+#[allow(dead_code)]
+impl CompassDirection {
+    pub fn directions() -> [CompassDirection; 8] {
+        [
+            CompassDirection::North,
+            CompassDirection::NorthEast,
+            CompassDirection::East,
+            CompassDirection::SouthEast,
+            CompassDirection::South,
+            CompassDirection::SouthWest,
+            CompassDirection::West,
+            CompassDirection::NorthWest,
+        ]
+    }
+    pub fn diagonals() -> [CompassDirection; 4] {
+        [
+            CompassDirection::NorthEast,
+            CompassDirection::SouthEast,
+            CompassDirection::SouthWest,
+            CompassDirection::NorthWest,
+        ]
+    }
+    pub fn is_diagonal(&self) -> bool {
+        (*self as usize) % 2 == 1
+    }
+    pub fn offset(&self) -> Point<i64> {
+        match self {
+            CompassDirection::North => Point::new(0, -1),
+            CompassDirection::NorthEast => Point::new(1, -1),
+            CompassDirection::East => Point::new(1, 0),
+            CompassDirection::SouthEast => Point::new(1, 1),
+            CompassDirection::South => Point::new(0, 1),
+            CompassDirection::SouthWest => Point::new(-1, 1),
+            CompassDirection::West => Point::new(-1, 0),
+            CompassDirection::NorthWest => Point::new(-1, -1),
+        }
+    }
+    pub fn turn_right_45(&self) -> Self {
+        self.rotate(1)
+    }
+    pub fn turn_left_45(&self) -> Self {
+        self.rotate(7)
+    }
+    pub fn turn_right(&self) -> Self {
+        self.rotate(2)
+    }
+    pub fn turn_left(&self) -> Self {
+        self.rotate(6)
+    }
+    pub fn opposite(&self) -> Self {
+        self.rotate(4)
+    }
+    fn rotate(&self, steps: usize) -> Self {
+        Self::directions()[(*self as usize + steps) % 8]
+    }
+}
+
+impl From<Direction> for CompassDirection {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Up => CompassDirection::North,
+            Direction::Down => CompassDirection::South,
+            Direction::Left => CompassDirection::West,
+            Direction::Right => CompassDirection::East,
+        }
+    }
+}
+
+impl TryFrom<CompassDirection> for Direction {
+    type Error = ();
+
+    fn try_from(value: CompassDirection) -> Result<Self, Self::Error> {
+        match value {
+            CompassDirection::North => Ok(Direction::Up),
+            CompassDirection::South => Ok(Direction::Down),
+            CompassDirection::West => Ok(Direction::Left),
+            CompassDirection::East => Ok(Direction::Right),
+            _ => Err(()),
+        }
+    }
 }
 
 impl<T: Numeric> Add for &Point<T> {
@@ -377,24 +928,55 @@ impl<T: Numeric> Sub for &Point<T> {
     }
 }
 
-impl<T: Numeric + Display> Display for Point<T> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({}, {})", self.x, self.y)
+impl<T: Numeric> Add for Point<T> {
+    type Output = Point<T>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Point {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
     }
 }
 
-#[allow(dead_code)]
-pub trait MultiplyByI32 {
-    type Output;
-    fn multiply_by_i32(self, rhs: i32) -> Self::Output;
+impl<T: Numeric> Sub for Point<T> {
+    type Output = Point<T>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Point {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
 }
 
-impl<T> MultiplyByI32 for &Point<T>
-where
-    T: Numeric + Mul<i32, Output = T>,
-{
+impl<T: Numeric> AddAssign for Point<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Numeric> SubAssign for Point<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: NumericNeg> Neg for Point<T> {
+    type Output = Point<T>;
+
+    fn neg(self) -> Self::Output {
+        Point {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl<T: Numeric> Mul<T> for Point<T> {
     type Output = Point<T>;
-    fn multiply_by_i32(self, rhs: i32) -> Self::Output {
+
+    fn mul(self, rhs: T) -> Self::Output {
         Point {
             x: self.x * rhs,
             y: self.y * rhs,
@@ -402,24 +984,112 @@ where
     }
 }
 
-pub trait MultiplyByI64 {
-    type Output;
-    fn multiply_by_i64(self, rhs: i64) -> Self::Output;
+impl<T: Numeric + Display> Display for Point<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
 }
 
-impl<T> MultiplyByI64 for &Point<T>
+// accepts the Display form "(x, y)" as well as a bare "x,y"
+impl<T: Numeric + FromStr> FromStr for Point<T>
 where
-    T: Numeric + Mul<i64, Output = T>,
+    T::Err: Display,
 {
-    type Output = Point<T>;
-    fn multiply_by_i64(self, rhs: i64) -> Self::Output {
-        Point {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim();
+        let inner = trimmed
+            .strip_prefix('(')
+            .and_then(|inner| inner.strip_suffix(')'))
+            .unwrap_or(trimmed);
+        let (x, y) = inner
+            .split_once(',')
+            .ok_or_else(|| ParseError::missing(s, "a point like (x, y)"))?;
+        Ok(Point {
+            x: parse_str(s, x.trim())?,
+            y: parse_str(s, y.trim())?,
+        })
+    }
+}
+
+#[derive(Copy, Clone, Default, Hash, PartialEq, Eq, Ord, PartialOrd, Debug)]
+pub struct Point3<T: Numeric> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+#[allow(dead_code)]
+impl<T: Numeric> Point3<T> {
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+    pub fn manhattan_distance(&self, other: &Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y) + abs_diff(self.z, other.z)
+    }
+    pub fn chebyshev_distance(&self, other: &Self) -> T {
+        max(
+            max(abs_diff(self.x, other.x), abs_diff(self.y, other.y)),
+            abs_diff(self.z, other.z),
+        )
+    }
+}
+
+impl<T: Numeric> Add for Point3<T> {
+    type Output = Point3<T>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Point3 {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+            z: self.z + rhs.z,
+        }
+    }
+}
+
+impl<T: Numeric> Sub for Point3<T> {
+    type Output = Point3<T>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Point3 {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+            z: self.z - rhs.z,
+        }
+    }
+}
+
+impl<T: NumericNeg> Neg for Point3<T> {
+    type Output = Point3<T>;
+
+    fn neg(self) -> Self::Output {
+        Point3 {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+}
+
+impl<T: Numeric> Mul<T> for Point3<T> {
+    type Output = Point3<T>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Point3 {
             x: self.x * rhs,
             y: self.y * rhs,
+            z: self.z * rhs,
         }
     }
 }
 
+impl<T: Numeric + Display> Display for Point3<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
 pub trait Invertible {
     fn invert(self) -> Self;
 }
@@ -468,6 +1138,27 @@ impl TryFrom<char> for Direction {
     }
 }
 
+impl FromStr for Direction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "Up" => Ok(Direction::Up),
+            "Down" => Ok(Direction::Down),
+            "Left" => Ok(Direction::Left),
+            "Right" => Ok(Direction::Right),
+            trimmed => {
+                let mut chars = trimmed.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Direction::try_from(c).ok(),
+                    _ => None,
+                }
+                .ok_or_else(|| ParseError::at_str(s, trimmed, "expected a direction"))
+            }
+        }
+    }
+}
+
 impl Display for Direction {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -535,4 +1226,335 @@ impl<T: Display, V: Display> Display for GridDisplay<'_, T, V> {
     }
 }
 
-impl Node for Point<usize> {}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::hint::black_box;
+    use std::time::{Duration, Instant};
+
+    fn sample_grid() -> Grid<char> {
+        Grid::from_iter(["#..#", "#.O#", "####"].into_iter().map(|row| row.chars()))
+    }
+
+    #[test]
+    fn test_flat_storage_rows_and_index() {
+        let mut grid = sample_grid();
+        assert_eq!((grid.len_x(), grid.len_y()), (4, 3));
+        assert_eq!(grid[&Point::new(2, 1)], 'O');
+        assert_eq!(grid.row(1), &['#', '.', 'O', '#']);
+        assert_eq!(grid.rows().count(), 3);
+        assert_eq!(grid.get(&Point::new(4, 0)), None);
+
+        grid[&Point::new(1, 0)] = 'x';
+        grid.row_mut(2)[3] = 'y';
+        assert_eq!(grid.get(&Point::new(1, 0)), Some(&'x'));
+        assert_eq!(grid.get(&Point::new(3, 2)), Some(&'y'));
+        assert_eq!(
+            grid.iter().map(|(_, &c)| c).collect::<String>(),
+            "#x.##.O####y"
+        );
+    }
+
+    #[test]
+    fn test_clone_from_resizes() {
+        let source = sample_grid();
+        let mut same_size = Grid::<char>::new(4, 3);
+        let buffer = same_size.as_slice().as_ptr();
+        same_size.clone_from(&source);
+        assert_eq!(same_size.as_slice().as_ptr(), buffer);
+        assert_eq!(
+            same_size.iter().collect::<Vec<_>>(),
+            source.iter().collect::<Vec<_>>()
+        );
+
+        let mut other_size = Grid::<char>::new(2, 2);
+        other_size.clone_from(&source);
+        assert_eq!((other_size.len_x(), other_size.len_y()), (4, 3));
+        assert_eq!(other_size.row(2), source.row(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "All rows must be the same length")]
+    fn test_from_iter_rejects_ragged_rows() {
+        Grid::from_iter(["##", "#"].into_iter().map(|row| row.chars()));
+    }
+
+    fn to_rows(grid: &Grid<char>) -> Vec<String> {
+        grid.rows().map(|row| row.iter().collect()).collect()
+    }
+
+    #[test]
+    fn test_rotations_and_flips() {
+        let grid = Grid::from_iter(["abc", "def"].into_iter().map(|row| row.chars()));
+        assert_eq!(to_rows(&grid.rotated_90()), vec!["da", "eb", "fc"]);
+        assert_eq!(to_rows(&grid.rotated_180()), vec!["fed", "cba"]);
+        assert_eq!(to_rows(&grid.rotated_270()), vec!["cf", "be", "ad"]);
+        assert_eq!(to_rows(&grid.flipped_horizontal()), vec!["cba", "fed"]);
+        assert_eq!(to_rows(&grid.flipped_vertical()), vec!["def", "abc"]);
+        assert_eq!(to_rows(&grid.transposed()), vec!["ad", "be", "cf"]);
+        assert_eq!(
+            to_rows(&grid.rotated_90().rotated_90().rotated_90().rotated_90()),
+            to_rows(&grid)
+        );
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.collect::<String>())
+                .collect::<Vec<_>>(),
+            vec!["ad", "be", "cf"]
+        );
+    }
+
+    #[test]
+    fn test_views() {
+        let grid = Grid::from_iter(["abcd", "efgh", "ijkl"].into_iter().map(|row| row.chars()));
+        let view = grid.view(&Point::new(1, 1), 2, 2).unwrap();
+        assert_eq!(view.row(1), &['j', 'k']);
+        assert_eq!(view.get(&Point::new(1, 0)), Some(&'g'));
+        assert_eq!(view.get(&Point::new(2, 0)), None);
+        assert_eq!(view.column(0).collect::<String>(), "fj");
+        assert_eq!(to_rows(&view.to_grid()), vec!["fg", "jk"]);
+        assert!(grid.view(&Point::new(3, 0), 2, 1).is_none());
+        assert_eq!(grid.views(2, 2).count(), 6);
+        assert_eq!(grid.views(5, 1).count(), 0);
+    }
+
+    #[test]
+    fn test_iterators() {
+        let mut grid = sample_grid();
+        let mut cells = grid.iter();
+        assert_eq!(cells.len(), 12);
+        assert_eq!(cells.next(), Some((Point::new(0, 0), &'#')));
+        assert_eq!(cells.next_back(), Some((Point::new(3, 2), &'#')));
+        assert_eq!(cells.nth(5), Some((Point::new(2, 1), &'O')));
+        assert_eq!(cells.len(), 4);
+        assert_eq!(cells.count(), 4);
+
+        for (point, value) in grid.iter_mut() {
+            if point.y == 0 && *value == '.' {
+                *value = 'x';
+            }
+        }
+        assert_eq!(to_rows(&grid), vec!["#xx#", "#.O#", "####"]);
+
+        let walls = grid.map(|_, &c| c == '#');
+        assert_eq!((walls.len_x(), walls.len_y()), (4, 3));
+        assert_eq!(walls.iter().filter(|(_, &wall)| wall).count(), 8);
+
+        let owned: Vec<_> = grid.into_iter().rev().take(2).collect();
+        assert_eq!(
+            owned,
+            vec![(Point::new(3, 2), '#'), (Point::new(2, 2), '#')]
+        );
+        assert_eq!(Grid::<char>::new(0, 3).iter().next(), None);
+    }
+
+    #[test]
+    fn test_compass_directions() {
+        let north = CompassDirection::North;
+        assert_eq!(north.turn_right_45(), CompassDirection::NorthEast);
+        assert_eq!(north.turn_left_45(), CompassDirection::NorthWest);
+        assert_eq!(north.turn_right().turn_right(), north.opposite());
+        assert_eq!(CompassDirection::West.turn_left(), CompassDirection::South);
+        assert!(CompassDirection::diagonals()
+            .iter()
+            .all(|d| d.is_diagonal()));
+        assert!(CompassDirection::directions()
+            .iter()
+            .all(|d| d.offset() + d.opposite().offset() == Point::new(0, 0)));
+        for direction in Direction::directions() {
+            let compass = CompassDirection::from(direction);
+            assert_eq!(compass.offset(), direction.offset());
+            assert_eq!(Direction::try_from(compass), Ok(direction));
+        }
+        assert_eq!(Direction::try_from(CompassDirection::SouthWest), Err(()));
+
+        let point = Point::new(1usize, 0);
+        assert_eq!(point.move_to(Direction::Up), None);
+        assert_eq!(
+            point.move_to(CompassDirection::SouthWest),
+            Some(Point::new(0, 1))
+        );
+    }
+
+    #[test]
+    fn test_rays_and_compass_neighbors() {
+        let grid = sample_grid();
+        let ray: Vec<_> = grid
+            .ray(&Point::new(0, 0), CompassDirection::SouthEast)
+            .collect();
+        assert_eq!(
+            ray,
+            vec![Point::new(0, 0), Point::new(1, 1), Point::new(2, 2)]
+        );
+        assert_eq!(grid.ray(&Point::new(3, 1), Direction::Right).count(), 1);
+        assert_eq!(grid.ray(&Point::new(4, 0), Direction::Left).count(), 0);
+
+        let neighbors: Vec<_> = grid.compass_neighbors(&Point::new(0, 2)).collect();
+        assert_eq!(
+            neighbors,
+            vec![
+                (CompassDirection::North, Point::new(0, 1)),
+                (CompassDirection::NorthEast, Point::new(1, 1)),
+                (CompassDirection::East, Point::new(1, 2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_point_arithmetic_and_distances() {
+        let mut point = Point::new(3i64, -2);
+        let offset = Point::new(-1, 4);
+        assert_eq!(point + offset, Point::new(2, 2));
+        assert_eq!(point - offset, Point::new(4, -6));
+        assert_eq!(-point, Point::new(-3, 2));
+        assert_eq!(offset * 3, Point::new(-3, 12));
+        point += offset;
+        point -= Point::new(2, 2);
+        assert_eq!(point, Point::new(0, 0));
+
+        assert_eq!(Point::new(1, 5).manhattan_distance(&Point::new(4, 1)), 7);
+        assert_eq!(
+            Point::new(-1i64, 5).chebyshev_distance(&Point::new(2, -1)),
+            6
+        );
+        assert_eq!(
+            Point::new(1.5, 0.0).manhattan_distance(&Point::new(0.0, 2.0)),
+            3.5
+        );
+        assert_eq!(
+            Point::new(0i64, 0).move_to(CompassDirection::NorthWest),
+            Point::new(-1, -1)
+        );
+
+        assert_eq!(
+            Point::<usize>::try_from(Point::new(2i64, 7)),
+            Ok(Point::new(2, 7))
+        );
+        assert!(Point::<usize>::try_from(Point::new(2i64, -7)).is_err());
+        assert_eq!(
+            Point::<i64>::try_from(Point::new(2usize, 7)),
+            Ok(Point::new(2, 7))
+        );
+        assert!(Point::<i64>::try_from(Point::new(usize::MAX, 0)).is_err());
+    }
+
+    #[test]
+    fn test_point3() {
+        let a = Point3::new(1i64, -2, 3);
+        let b = Point3::new(4, 2, -1);
+        assert_eq!(a + b, Point3::new(5, 0, 2));
+        assert_eq!(b - a, Point3::new(3, 4, -4));
+        assert_eq!(-a * 2, Point3::new(-2, 4, -6));
+        assert_eq!(a.manhattan_distance(&b), 11);
+        assert_eq!(a.chebyshev_distance(&b), 4);
+        assert_eq!(a.to_string(), "(1, -2, 3)");
+    }
+
+    #[test]
+    fn test_torus_wraps() {
+        let torus = Torus::new(11, 7);
+        let velocity = Point::new(2, -3);
+        let mut point = Point::new(2, 4);
+        for _ in 0..5 {
+            point = torus.step(&point, &velocity, 1);
+        }
+        assert_eq!(point, Point::new(1, 3));
+        assert_eq!(
+            torus.step(&Point::new(2, 4), &velocity, 5),
+            Point::new(1, 3)
+        );
+        assert_eq!(
+            torus.step(&Point::new(2, 4), &velocity, -1),
+            Point::new(0, 0)
+        );
+        assert_eq!(torus.wrap(&Point::new(-1, 7)), Point::new(10, 0));
+        assert_eq!(
+            torus.move_to(&Point::new(0, 0), Direction::Up),
+            Point::new(0, 6)
+        );
+        assert!(torus
+            .neighbors(&Point::new(10, 6))
+            .contains(&Point::new(0, 6)));
+    }
+
+    #[test]
+    fn test_torus_quadrants() {
+        let torus = Torus::new(11, 7);
+        assert_eq!(torus.quadrant(&Point::new(0, 0)), Some(Quadrant::NorthWest));
+        assert_eq!(torus.quadrant(&Point::new(6, 2)), Some(Quadrant::NorthEast));
+        assert_eq!(torus.quadrant(&Point::new(4, 4)), Some(Quadrant::SouthWest));
+        assert_eq!(torus.quadrant(&Point::new(5, 4)), None);
+        assert_eq!(torus.quadrant(&Point::new(4, 3)), None);
+        let points = [Point::new(0, 0), Point::new(10, 6), Point::new(5, 5)];
+        assert_eq!(
+            torus.quadrant_counts(points.iter().map(|p| (p, 2))),
+            [2, 0, 0, 2]
+        );
+        assert_eq!(
+            Torus::new(9, 4).partition(&Point::new(7, 3), 3, 2),
+            Some(Point::new(2, 1))
+        );
+    }
+
+    // The previous row-per-allocation layout, kept as a baseline for `bench_grid_storage`.
+    #[derive(Clone)]
+    struct NestedGrid<T>(Box<[Box<[T]>]>);
+
+    impl<T: Clone + Default> NestedGrid<T> {
+        fn new(size_x: usize, size_y: usize) -> Self {
+            Self(
+                (0..size_y)
+                    .map(|_| vec![T::default(); size_x].into())
+                    .collect(),
+            )
+        }
+        fn get(&self, point: &Point<usize>) -> Option<&T> {
+            self.0.get(point.y).and_then(|row| row.get(point.x))
+        }
+    }
+
+    fn time(iterations: usize, mut f: impl FnMut()) -> Duration {
+        let start = Instant::now();
+        for _ in 0..iterations {
+            f();
+        }
+        start.elapsed()
+    }
+
+    // cargo test --release bench_grid_storage -- --ignored --nocapture
+    #[test]
+    #[ignore]
+    fn bench_grid_storage() {
+        const SIZE: usize = 141;
+        const ITERATIONS: usize = 2_000;
+        let points: Vec<_> = (0..SIZE)
+            .flat_map(|y| (0..SIZE).map(move |x| Point::new((x * 7 + y) % SIZE, y)))
+            .collect();
+
+        let flat = Grid::<u64>::new(SIZE, SIZE);
+        let nested = NestedGrid::<u64>::new(SIZE, SIZE);
+
+        let mut flat_target = flat.clone();
+        let buffer = flat_target.as_slice().as_ptr();
+        let flat_clone = time(ITERATIONS, || flat_target.clone_from(black_box(&flat)));
+        let mut nested_target = nested.clone();
+        let nested_clone = time(ITERATIONS, || nested_target.clone_from(black_box(&nested)));
+
+        let flat_get = time(ITERATIONS, || {
+            black_box(points.iter().filter_map(|p| flat.get(p)).sum::<u64>());
+        });
+        let nested_get = time(ITERATIONS, || {
+            black_box(points.iter().filter_map(|p| nested.get(p)).sum::<u64>());
+        });
+
+        println!(
+            "clone_from: flat {:?} vs nested {:?}",
+            flat_clone, nested_clone
+        );
+        println!("get:        flat {:?} vs nested {:?}", flat_get, nested_get);
+
+        // the point of the flat layout: clone_from copies into the existing buffer
+        assert_eq!(flat_target.as_slice().as_ptr(), buffer);
+        assert_eq!(flat_target, flat);
+    }
+}
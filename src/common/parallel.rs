@@ -0,0 +1,85 @@
+use crate::common::models::{Grid, Point};
+use rayon::prelude::*;
+
+#[allow(dead_code)]
+impl<T: Sync> Grid<T> {
+    pub fn par_rows(&self) -> impl IndexedParallelIterator<Item = &[T]> + '_ {
+        self.as_slice()
+            // rayon panics on a chunk size of 0; without columns there are no chunks to cut
+            .par_chunks(self.len_x().max(1))
+            .chain((0..empty_rows(self)).into_par_iter().map(|_| &[][..]))
+    }
+    pub fn par_iter(&self) -> impl IndexedParallelIterator<Item = (Point<usize>, &T)> + '_ {
+        let len_x = self.len_x();
+        self.as_slice()
+            .par_iter()
+            .enumerate()
+            .map(move |(index, value)| (point_at(len_x, index), value))
+    }
+}
+
+#[allow(dead_code)]
+impl<T: Send> Grid<T> {
+    pub fn par_rows_mut(&mut self) -> impl IndexedParallelIterator<Item = &mut [T]> + '_ {
+        // as in par_rows, the 1 only avoids rayon's panic on a chunk size of 0
+        let len_x = self.len_x().max(1);
+        let empty_rows = empty_rows(self);
+        self.as_mut_slice()
+            .par_chunks_mut(len_x)
+            .chain((0..empty_rows).into_par_iter().map(|_| &mut [][..]))
+    }
+    pub fn par_iter_mut(
+        &mut self,
+    ) -> impl IndexedParallelIterator<Item = (Point<usize>, &mut T)> + '_ {
+        let len_x = self.len_x();
+        self.as_mut_slice()
+            .par_iter_mut()
+            .enumerate()
+            .map(move |(index, value)| (point_at(len_x, index), value))
+    }
+}
+
+// a grid without columns still has len_y rows, like rows() yields
+fn empty_rows<T>(grid: &Grid<T>) -> usize {
+    match grid.len_x() {
+        0 => grid.len_y(),
+        _ => 0,
+    }
+}
+
+fn point_at(len_x: usize, index: usize) -> Point<usize> {
+    Point {
+        x: index % len_x,
+        y: index / len_x,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parallel_matches_sequential() {
+        let mut grid = Grid::from_iter((0..5).map(|y| (0..7).map(move |x| x * 10 + y)));
+        let sums: Vec<usize> = grid.par_rows().map(|row| row.iter().sum()).collect();
+        assert_eq!(
+            sums,
+            grid.rows()
+                .map(|row| row.iter().sum())
+                .collect::<Vec<usize>>()
+        );
+        let cells: Vec<_> = grid.par_iter().map(|(p, &v)| (p, v)).collect();
+        assert_eq!(cells, grid.iter().map(|(p, &v)| (p, v)).collect::<Vec<_>>());
+
+        grid.par_iter_mut()
+            .for_each(|(point, value)| *value = point.x * point.y);
+        assert_eq!(grid[&Point::new(6, 4)], 24);
+        grid.par_rows_mut().for_each(|row| row.reverse());
+        assert_eq!(grid[&Point::new(0, 4)], 24);
+
+        let mut empty: Grid<usize> = Grid::new(0, 3);
+        assert_eq!(empty.par_rows().count(), empty.rows().count());
+        assert_eq!(empty.par_rows_mut().count(), 3);
+        assert!(empty.par_rows().all(|row| row.is_empty()));
+    }
+}
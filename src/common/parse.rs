@@ -0,0 +1,178 @@
+use crate::common::parse_error::{parse_nested, parse_str, ParseError};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::fmt::Display;
+use std::str::FromStr;
+
+static INTEGER: Lazy<Regex> = Lazy::new(|| Regex::new(r"-?\d+").unwrap());
+
+pub fn sections(input: &str) -> Vec<&str> {
+    let mut sections = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut end = 0;
+    let mut offset = 0;
+    for line in input.split_inclusive('\n') {
+        let content = line.trim_end_matches(['\n', '\r']);
+        if content.trim().is_empty() {
+            if let Some(start) = start.take() {
+                sections.push(&input[start..end]);
+            }
+        } else {
+            start.get_or_insert(offset);
+            end = offset + content.len();
+        }
+        offset += line.len();
+    }
+    if let Some(start) = start {
+        sections.push(&input[start..end]);
+    }
+    sections
+}
+
+pub fn sections_n<const N: usize>(input: &str) -> Result<[&str; N], ParseError> {
+    let sections = sections(input);
+    let count = sections.len();
+    sections.try_into().map_err(|_| {
+        ParseError::at(
+            input,
+            input.len(),
+            format!(
+                "expected {} sections separated by blank lines but got {}",
+                N, count
+            ),
+        )
+    })
+}
+
+pub fn lines<T>(input: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr<Err = ParseError>,
+{
+    lines_in(input, input)
+}
+
+// parses the lines of a section cut from input, with errors located in the whole input
+pub fn lines_in<T>(input: &str, section: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr<Err = ParseError>,
+{
+    section
+        .lines()
+        .map(|line| parse_nested(input, line))
+        .collect()
+}
+
+pub fn integers<T>(text: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    INTEGER
+        .find_iter(text)
+        .map(|m| parse_str(text, m.as_str()))
+        .collect()
+}
+
+pub fn integers_n<T, const N: usize>(text: &str) -> Result<[T; N], ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let integers = integers(text)?;
+    let count = integers.len();
+    integers.try_into().map_err(|_| {
+        ParseError::at(
+            text,
+            0,
+            format!("expected {} integers but got {}", N, count),
+        )
+    })
+}
+
+pub fn key_value<'a>(input: &'a str, separator: &str) -> Result<(&'a str, &'a str), ParseError> {
+    let (key, value) = input
+        .split_once(separator)
+        .ok_or_else(|| ParseError::missing(input, &format!("'{}'", separator)))?;
+    Ok((key.trim(), value.trim()))
+}
+
+pub fn key_values<'a, T>(input: &'a str, separator: &str) -> Result<Vec<(&'a str, T)>, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    input
+        .lines()
+        .map(|line| {
+            let (key, value) = key_value(line, separator).map_err(|e| e.relocate(input, line))?;
+            Ok((key, parse_str(input, value)?))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Pair(i32, i32);
+
+    impl FromStr for Pair {
+        type Err = ParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let [a, b] = integers_n(s)?;
+            Ok(Self(a, b))
+        }
+    }
+
+    #[test]
+    fn test_sections() {
+        let input = "a\nb\n\nc\r\n\r\n\n d \n";
+        assert_eq!(sections(input), vec!["a\nb", "c", " d "]);
+        assert!(sections_n::<2>(input).is_err());
+        let [first, second] = sections_n("1|2\n\n3,4").unwrap();
+        assert_eq!((first, second), ("1|2", "3,4"));
+    }
+
+    #[test]
+    fn test_lines_report_line_numbers() {
+        assert_eq!(
+            lines::<Pair>("1,2\n-3,4").unwrap(),
+            vec![Pair(1, 2), Pair(-3, 4)]
+        );
+        let error = lines::<Pair>("1,2\n3,4\n5").unwrap_err();
+        assert_eq!((error.line(), error.column()), (3, 1));
+
+        let input = "1,2\n\n3,4\n5";
+        let [_, second] = sections_n(input).unwrap();
+        let error = lines_in::<Pair>(input, second).unwrap_err();
+        assert_eq!((error.line(), error.column()), (4, 1));
+    }
+
+    #[test]
+    fn test_integers() {
+        assert_eq!(integers::<i64>("p=0,4 v=3,-3").unwrap(), vec![0, 4, 3, -3]);
+        assert_eq!(
+            integers_n::<i64, 2>("Button A: X+94, Y-34").unwrap(),
+            [94, -34]
+        );
+        assert_eq!(integers::<u32>("no numbers here").unwrap(), vec![]);
+        let error = integers::<u8>("1 2 300").unwrap_err();
+        assert_eq!(error.column(), 5);
+    }
+
+    #[test]
+    fn test_key_values() {
+        assert_eq!(
+            key_value("Program: 0,1,5", ":").unwrap(),
+            ("Program", "0,1,5")
+        );
+        assert_eq!(
+            key_values::<u64>("Register A: 729\nRegister B: 0", ":").unwrap(),
+            vec![("Register A", 729), ("Register B", 0)]
+        );
+        let error = key_values::<u64>("x00: 1\nx01 1", ":").unwrap_err();
+        assert_eq!((error.line(), error.column()), (2, 6));
+    }
+}
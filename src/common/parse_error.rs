@@ -0,0 +1,184 @@
+use crate::common::models::Point;
+use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
+
+#[derive(thiserror::Error, Clone, PartialEq, Eq)]
+#[error("{}", Diagnostic(self))]
+pub struct ParseError {
+    message: String,
+    line: usize,
+    column: usize,
+    snippet: String,
+}
+
+impl ParseError {
+    pub fn at(input: &str, offset: usize, message: impl Into<String>) -> Self {
+        let offset = floor_char_boundary(input, offset.min(input.len()));
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+        Self {
+            message: message.into(),
+            line: input[..line_start].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            snippet: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+        }
+    }
+    pub fn at_str(input: &str, part: &str, message: impl Into<String>) -> Self {
+        Self::at(input, offset_of(input, part), message)
+    }
+    pub fn at_point(input: &str, point: &Point<usize>, message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            line: point.y + 1,
+            column: point.x + 1,
+            snippet: input.lines().nth(point.y).unwrap_or_default().to_string(),
+        }
+    }
+    pub fn invalid_char(input: &str, point: &Point<usize>, c: char) -> Self {
+        Self::at_point(input, point, format!("invalid character '{}'", c))
+    }
+    pub fn missing(input: &str, what: &str) -> Self {
+        Self::at(input, input.len(), format!("expected {}", what))
+    }
+    pub fn relocate(self, input: &str, part: &str) -> Self {
+        let start = Self::at(input, offset_of(input, part), "");
+        let line = start.line + self.line - 1;
+        let column = if self.line == 1 {
+            start.column + self.column - 1
+        } else {
+            self.column
+        };
+        Self {
+            message: self.message,
+            line,
+            column,
+            snippet: input.lines().nth(line - 1).unwrap_or_default().to_string(),
+        }
+    }
+    #[allow(dead_code)]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+    #[allow(dead_code)]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+    #[allow(dead_code)]
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+pub fn parse_str<T>(input: &str, part: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    part.parse()
+        .map_err(|e| ParseError::at_str(input, part, format!("cannot parse '{}': {}", part, e)))
+}
+
+pub fn parse_nested<T>(input: &str, part: &str) -> Result<T, ParseError>
+where
+    T: FromStr<Err = ParseError>,
+{
+    part.parse()
+        .map_err(|e: ParseError| e.relocate(input, part))
+}
+
+fn offset_of(input: &str, part: &str) -> usize {
+    let start = input.as_ptr() as usize;
+    let part_start = part.as_ptr() as usize;
+    if part_start >= start && part_start <= start + input.len() {
+        part_start - start
+    } else {
+        input.find(part).unwrap_or(0)
+    }
+}
+
+fn floor_char_boundary(input: &str, mut offset: usize) -> usize {
+    while !input.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    offset
+}
+
+struct Diagnostic<'a>(&'a ParseError);
+
+impl Display for Diagnostic<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let error = self.0;
+        let line_number = error.line.to_string();
+        let gutter = " ".repeat(line_number.len());
+        writeln!(f, "error: {}", error.message)?;
+        writeln!(
+            f,
+            "{}--> line {}, column {}",
+            gutter, error.line, error.column
+        )?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", line_number, error.snippet)?;
+        write!(f, "{} | {}^", gutter, " ".repeat(error.column - 1))
+    }
+}
+
+impl Debug for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "\n{}", Diagnostic(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_at_offset() {
+        let input = "#..#\n#.?#\n####";
+        let error = ParseError::at(input, 7, "invalid character '?'");
+        assert_eq!(error.line(), 2);
+        assert_eq!(error.column(), 3);
+        assert_eq!(error.snippet, "#.?#");
+    }
+
+    #[test]
+    fn test_at_str_and_missing() {
+        let input = "x00 AND y00 -> z00\nx01 NAND y01 -> z01";
+        let part = input.split_whitespace().nth(6).unwrap();
+        let error = ParseError::at_str(input, part, "invalid operation");
+        assert_eq!((error.line(), error.column()), (2, 5));
+
+        let error = ParseError::missing("x00 AND", "second operand");
+        assert_eq!((error.line(), error.column()), (1, 8));
+    }
+
+    #[test]
+    fn test_parse_str_points_at_part() {
+        let input = "190: 10 1x";
+        let part = input.split_whitespace().nth(2).unwrap();
+        let error = parse_str::<i64>(input, part).unwrap_err();
+        assert_eq!((error.line(), error.column()), (1, 9));
+    }
+
+    #[test]
+    fn test_relocate() {
+        let input = "p=0,4 v=3,-3\np=6,3 v=-1,-3";
+        let line = input.lines().nth(1).unwrap();
+        let error = ParseError::at(line, 8, "bad velocity").relocate(input, line);
+        assert_eq!((error.line(), error.column()), (2, 9));
+        assert_eq!(error.snippet, "p=6,3 v=-1,-3");
+    }
+
+    #[test]
+    fn test_display_renders_diagnostic() {
+        let error = ParseError::invalid_char("#.#\n#?#", &Point::new(1, 1), '?');
+        assert_eq!(
+            error.to_string(),
+            "error: invalid character '?'\n --> line 2, column 2\n  |\n2 | #?#\n  |  ^"
+        );
+    }
+}
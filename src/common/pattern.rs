@@ -0,0 +1,253 @@
+use crate::common::models::{Grid, GridView, Point};
+
+#[derive(Copy, Clone, Hash, PartialEq, Eq, Debug)]
+pub enum Orientation {
+    Identity,
+    Rotated90,
+    Rotated180,
+    Rotated270,
+    Flipped,
+    FlippedRotated90,
+    FlippedRotated180,
+    FlippedRotated270,
+}
+
+#[allow(dead_code)]
+impl Orientation {
+    pub fn rotations() -> [Self; 4] {
+        [
+            Self::Identity,
+            Self::Rotated90,
+            Self::Rotated180,
+            Self::Rotated270,
+        ]
+    }
+    pub fn all() -> [Self; 8] {
+        [
+            Self::Identity,
+            Self::Rotated90,
+            Self::Rotated180,
+            Self::Rotated270,
+            Self::Flipped,
+            Self::FlippedRotated90,
+            Self::FlippedRotated180,
+            Self::FlippedRotated270,
+        ]
+    }
+    pub fn is_flipped(&self) -> bool {
+        matches!(
+            self,
+            Self::Flipped
+                | Self::FlippedRotated90
+                | Self::FlippedRotated180
+                | Self::FlippedRotated270
+        )
+    }
+}
+
+#[allow(dead_code)]
+impl<T: Clone> Grid<T> {
+    // flips horizontally first, then rotates clockwise
+    pub fn oriented(&self, orientation: Orientation) -> Self {
+        match orientation {
+            Orientation::Identity => self.clone(),
+            Orientation::Rotated90 => self.rotated_90(),
+            Orientation::Rotated180 => self.rotated_180(),
+            Orientation::Rotated270 => self.rotated_270(),
+            Orientation::Flipped => self.flipped_horizontal(),
+            Orientation::FlippedRotated90 => self.flipped_horizontal().rotated_90(),
+            Orientation::FlippedRotated180 => self.flipped_horizontal().rotated_180(),
+            Orientation::FlippedRotated270 => self.flipped_horizontal().rotated_270(),
+        }
+    }
+}
+
+// a template grid where `None` cells match anything
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pattern<T> {
+    cells: Grid<Option<T>>,
+}
+
+#[allow(dead_code)]
+impl<T> Pattern<T> {
+    pub fn new(cells: Grid<Option<T>>) -> Self {
+        Self { cells }
+    }
+    pub fn from_grid(grid: &Grid<T>, is_wildcard: impl Fn(&T) -> bool) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(grid.map(|_, value| (!is_wildcard(value)).then(|| value.clone())))
+    }
+    pub fn len_x(&self) -> usize {
+        self.cells.len_x()
+    }
+    pub fn len_y(&self) -> usize {
+        self.cells.len_y()
+    }
+    pub fn cells(&self) -> &Grid<Option<T>> {
+        &self.cells
+    }
+    pub fn oriented(&self, orientation: Orientation) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(self.cells.oriented(orientation))
+    }
+    // cells of the pattern placed at `origin` that differ from or fall outside the grid
+    pub fn mismatches<'a>(
+        &'a self,
+        grid: &'a Grid<T>,
+        origin: &Point<usize>,
+    ) -> impl Iterator<Item = Point<usize>> + 'a
+    where
+        T: PartialEq,
+    {
+        let origin = *origin;
+        self.cells.iter().filter_map(move |(point, cell)| {
+            let expected = cell.as_ref()?;
+            (grid.get(&(origin + point)) != Some(expected)).then_some(point)
+        })
+    }
+    pub fn matches_at(&self, grid: &Grid<T>, origin: &Point<usize>) -> bool
+    where
+        T: PartialEq,
+    {
+        grid.view(origin, self.len_x(), self.len_y())
+            .is_some_and(|view| self.matches_view(&view))
+    }
+    fn matches_view(&self, view: &GridView<'_, T>) -> bool
+    where
+        T: PartialEq,
+    {
+        self.cells
+            .iter()
+            .all(|(point, cell)| cell.as_ref().is_none_or(|c| view.get(&point) == Some(c)))
+    }
+}
+
+#[allow(dead_code)]
+impl Pattern<char> {
+    pub fn with_wildcard(s: &str, wildcard: char) -> Self {
+        Self::new(Grid::from_iter(
+            s.lines()
+                .map(|line| line.chars().map(|c| (c != wildcard).then_some(c))),
+        ))
+    }
+}
+
+#[derive(Copy, Clone, Hash, PartialEq, Eq, Debug)]
+pub struct PatternMatch {
+    pub origin: Point<usize>,
+    pub orientation: Orientation,
+}
+
+#[allow(dead_code)]
+impl<T: PartialEq> Grid<T> {
+    // top left corners of every placement of the pattern
+    pub fn find_pattern(&self, pattern: &Pattern<T>) -> Vec<Point<usize>> {
+        self.views(pattern.len_x(), pattern.len_y())
+            .filter(|view| pattern.matches_view(view))
+            .map(|view| *view.origin())
+            .collect()
+    }
+    // orientations that turn the pattern into one already searched are skipped,
+    // so symmetric patterns are not counted twice
+    pub fn find_pattern_oriented(
+        &self,
+        pattern: &Pattern<T>,
+        orientations: &[Orientation],
+    ) -> Vec<PatternMatch>
+    where
+        T: Clone,
+    {
+        let mut searched: Vec<Pattern<T>> = Vec::new();
+        let mut matches = Vec::new();
+        for &orientation in orientations {
+            let oriented = pattern.oriented(orientation);
+            if searched.contains(&oriented) {
+                continue;
+            }
+            matches.extend(
+                self.find_pattern(&oriented)
+                    .into_iter()
+                    .map(|origin| PatternMatch {
+                        origin,
+                        orientation,
+                    }),
+            );
+            searched.push(oriented);
+        }
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_with_wildcards() {
+        let grid: Grid<char> = "#.#.\n.#..\n#.#.\n.#.#".parse().unwrap();
+        let cross = Pattern::with_wildcard("#?#\n?#?\n#?#", '?');
+        assert_eq!(grid.find_pattern(&cross), vec![Point::new(0, 0)]);
+        assert!(cross.matches_at(&grid, &Point::new(0, 0)));
+        assert!(!cross.matches_at(&grid, &Point::new(1, 1)));
+        assert!(!cross.matches_at(&grid, &Point::new(2, 2)));
+        assert_eq!(
+            cross
+                .mismatches(&grid, &Point::new(1, 1))
+                .collect::<Vec<_>>(),
+            vec![Point::new(2, 0)]
+        );
+    }
+
+    #[test]
+    fn test_find_oriented() {
+        let grid: Grid<char> = "ab..\n....\n..b.\n..a.".parse().unwrap();
+        let pattern = Pattern::with_wildcard("ab", '.');
+        let matches = grid.find_pattern_oriented(&pattern, &Orientation::all());
+        assert_eq!(
+            matches,
+            vec![
+                PatternMatch {
+                    origin: Point::new(0, 0),
+                    orientation: Orientation::Identity
+                },
+                PatternMatch {
+                    origin: Point::new(2, 2),
+                    orientation: Orientation::Rotated270
+                },
+            ]
+        );
+
+        let symmetric = Pattern::with_wildcard("b.b", '.');
+        let grid: Grid<char> = "b.b\n...\nb.b".parse().unwrap();
+        assert_eq!(
+            grid.find_pattern_oriented(&symmetric, &Orientation::all())
+                .len(),
+            4
+        );
+    }
+
+    #[test]
+    fn test_orientations() {
+        let grid: Grid<char> = "ab\ncd\nef".parse().unwrap();
+        assert_eq!(
+            grid.oriented(Orientation::Rotated90),
+            "eca\nfdb".parse::<Grid<char>>().unwrap()
+        );
+        assert_eq!(
+            grid.oriented(Orientation::FlippedRotated90),
+            "fdb\neca".parse::<Grid<char>>().unwrap()
+        );
+        let distinct: Vec<_> = Orientation::all()
+            .iter()
+            .map(|&o| grid.oriented(o))
+            .collect();
+        assert!(distinct
+            .iter()
+            .enumerate()
+            .all(|(i, a)| distinct[i + 1..].iter().all(|b| a != b)));
+    }
+}
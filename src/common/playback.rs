@@ -0,0 +1,273 @@
+use std::fmt::Display;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::str::FromStr;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use std::time::Duration;
+
+const CLEAR_SCREEN: &str = "\x1b[H\x1b[J";
+const HIDE_CURSOR: &str = "\x1b[?25l";
+const SHOW_CURSOR: &str = "\x1b[?25h";
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    TogglePause,
+    Forward,
+    Back,
+    Jump(usize),
+    Faster,
+    Slower,
+    Quit,
+}
+
+impl FromStr for Command {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "" | "p" => Ok(Command::TogglePause),
+            "n" => Ok(Command::Forward),
+            "b" => Ok(Command::Back),
+            "+" => Ok(Command::Faster),
+            "-" => Ok(Command::Slower),
+            "q" => Ok(Command::Quit),
+            other => other
+                .trim_start_matches('g')
+                .trim()
+                .parse()
+                .map(Command::Jump)
+                .map_err(|_| format!("unknown playback command '{}'", other)),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Playback {
+    frames: Vec<String>,
+    current: usize,
+    paused: bool,
+    delay: Duration,
+}
+
+#[allow(dead_code)]
+impl Playback {
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            frames: Vec::new(),
+            current: 0,
+            paused: false,
+            delay,
+        }
+    }
+    pub fn push_frame(&mut self, frame: impl Display) {
+        self.frames.push(frame.to_string());
+    }
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+    pub fn current(&self) -> usize {
+        self.current
+    }
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+    pub fn delay(&self) -> Duration {
+        self.delay
+    }
+    // advances one frame while playing and pauses on the last one
+    pub fn tick(&mut self) {
+        if self.paused {
+            return;
+        }
+        if self.current + 1 < self.frames.len() {
+            self.current += 1;
+        } else {
+            self.paused = true;
+        }
+    }
+    pub fn apply(&mut self, command: Command) -> bool {
+        match command {
+            Command::TogglePause => self.paused = !self.paused,
+            Command::Forward => {
+                self.paused = true;
+                self.current = (self.current + 1).min(self.frames.len().saturating_sub(1));
+            }
+            Command::Back => {
+                self.paused = true;
+                self.current = self.current.saturating_sub(1);
+            }
+            Command::Jump(frame) => {
+                self.paused = true;
+                self.current = frame.min(self.frames.len().saturating_sub(1));
+            }
+            Command::Faster => self.delay = (self.delay / 2).max(Duration::from_millis(1)),
+            Command::Slower => self.delay *= 2,
+            Command::Quit => return false,
+        }
+        true
+    }
+    pub fn render(&self, out: &mut impl Write) -> io::Result<()> {
+        write!(out, "{}", CLEAR_SCREEN)?;
+        if let Some(frame) = self.frames.get(self.current) {
+            writeln!(out, "{}", frame)?;
+        }
+        writeln!(
+            out,
+            "\nframe {}/{} {} every {} ms | enter/p: pause, n/b: step, g N: jump, +/-: speed, q: quit",
+            self.current,
+            self.frames.len().saturating_sub(1),
+            if self.paused { "paused," } else { "playing," },
+            self.delay.as_millis()
+        )?;
+        out.flush()
+    }
+    pub fn run(mut self) -> io::Result<()> {
+        let mut stdout = io::stdout();
+        if self.frames.is_empty() {
+            return Ok(());
+        }
+        if !stdout.is_terminal() {
+            log::warn!(
+                "stdout is not a terminal, skipping playback of {} frames",
+                self.len()
+            );
+            return Ok(());
+        }
+
+        let commands = read_commands();
+        // commands typed after the previous playback ended are not meant for this one
+        while commands.try_recv().is_ok() {}
+        write!(stdout, "{}", HIDE_CURSOR)?;
+        let _cursor = HiddenCursor;
+        loop {
+            self.render(&mut stdout)?;
+            let command = if self.paused {
+                match commands.recv() {
+                    Ok(command) => command,
+                    Err(_) => break,
+                }
+            } else {
+                match commands.recv_timeout(self.delay) {
+                    Ok(command) => command,
+                    Err(RecvTimeoutError::Timeout) => {
+                        self.tick();
+                        continue;
+                    }
+                    Err(RecvTimeoutError::Disconnected) => {
+                        std::thread::sleep(self.delay);
+                        self.tick();
+                        continue;
+                    }
+                }
+            };
+            if !self.apply(command) {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+// shows the cursor again however the playback ends, including on a failed write
+struct HiddenCursor;
+
+impl Drop for HiddenCursor {
+    fn drop(&mut self) {
+        let mut stdout = io::stdout();
+        let _ = write!(stdout, "{}", SHOW_CURSOR);
+        let _ = stdout.flush();
+    }
+}
+
+// a single stdin reader for the whole process, shared by every playback in turn,
+// so a finished playback cannot keep the stdin lock and swallow the next command
+fn read_commands() -> MutexGuard<'static, Receiver<Command>> {
+    static COMMANDS: OnceLock<Mutex<Receiver<Command>>> = OnceLock::new();
+    COMMANDS
+        .get_or_init(|| Mutex::new(spawn_reader()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn spawn_reader() -> Receiver<Command> {
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            match line.parse() {
+                Ok(command) => {
+                    if sender.send(command).is_err() {
+                        break;
+                    }
+                }
+                Err(e) => log::warn!("{}", e),
+            }
+        }
+    });
+    receiver
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn playback() -> Playback {
+        let mut playback = Playback::new(Duration::from_millis(100));
+        for i in 0..4 {
+            playback.push_frame(format!("frame {}", i));
+        }
+        playback
+    }
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!("".parse(), Ok(Command::TogglePause));
+        assert_eq!(" n ".parse(), Ok(Command::Forward));
+        assert_eq!("g 12".parse(), Ok(Command::Jump(12)));
+        assert_eq!("7".parse(), Ok(Command::Jump(7)));
+        assert!("x".parse::<Command>().is_err());
+    }
+
+    #[test]
+    fn test_ticks_and_steps() {
+        let mut playback = playback();
+        for _ in 0..3 {
+            playback.tick();
+        }
+        assert_eq!((playback.current(), playback.is_paused()), (3, false));
+        playback.tick();
+        assert_eq!((playback.current(), playback.is_paused()), (3, true));
+
+        assert!(playback.apply(Command::Back));
+        assert!(playback.apply(Command::Back));
+        assert_eq!(playback.current(), 1);
+        playback.apply(Command::Jump(99));
+        assert_eq!(playback.current(), 3);
+        playback.apply(Command::Jump(0));
+        playback.apply(Command::Forward);
+        assert_eq!(playback.current(), 1);
+        playback.apply(Command::TogglePause);
+        playback.tick();
+        assert_eq!(playback.current(), 2);
+
+        playback.apply(Command::Slower);
+        assert_eq!(playback.delay(), Duration::from_millis(200));
+        assert!(!playback.apply(Command::Quit));
+    }
+
+    #[test]
+    fn test_render_redraws_in_place() {
+        let mut playback = playback();
+        playback.apply(Command::Jump(2));
+        let mut out = Vec::new();
+        playback.render(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("\x1b[H\x1b[Jframe 2\n"));
+        assert!(out.contains("frame 2/3 paused,"));
+    }
+}
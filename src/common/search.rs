@@ -0,0 +1,350 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+pub trait Cost: Copy + Ord + Default + Add<Output = Self> {}
+impl<T> Cost for T where T: Copy + Ord + Default + Add<Output = T> {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path<N, C> {
+    pub nodes: Vec<N>,
+    pub cost: C,
+}
+
+#[derive(Clone, Debug)]
+pub struct SearchTree<N, C> {
+    pub distances: HashMap<N, C>,
+    parents: HashMap<N, N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Cost> SearchTree<N, C> {
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.distances.get(node).copied()
+    }
+    pub fn path_to(&self, goal: &N) -> Option<Path<N, C>> {
+        let cost = self.distance(goal)?;
+        Some(Path {
+            nodes: reconstruct_path(&self.parents, goal),
+            cost,
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ShortestPaths<N, C> {
+    pub cost: C,
+    pub goals: Vec<N>,
+    predecessors: HashMap<N, Vec<N>>,
+}
+
+impl<N: Clone + Eq + Hash, C: Cost> ShortestPaths<N, C> {
+    pub fn predecessors(&self, node: &N) -> &[N] {
+        self.predecessors.get(node).map_or(&[], |p| p.as_slice())
+    }
+    pub fn nodes_on_paths(&self) -> HashSet<N> {
+        let mut visited: HashSet<N> = self.goals.iter().cloned().collect();
+        let mut queue: VecDeque<N> = self.goals.iter().cloned().collect();
+        while let Some(node) = queue.pop_front() {
+            for previous in self.predecessors(&node) {
+                if visited.insert(previous.clone()) {
+                    queue.push_back(previous.clone());
+                }
+            }
+        }
+        visited
+    }
+    #[allow(dead_code)]
+    pub fn paths(&self) -> Vec<Vec<N>> {
+        let mut paths = Vec::new();
+        for goal in &self.goals {
+            self.collect_paths(vec![goal.clone()], &mut paths);
+        }
+        paths
+    }
+    fn collect_paths(&self, reversed: Vec<N>, paths: &mut Vec<Vec<N>>) {
+        let predecessors = self.predecessors(reversed.last().unwrap());
+        if predecessors.is_empty() {
+            paths.push(reversed.into_iter().rev().collect());
+            return;
+        }
+        for previous in predecessors {
+            let mut next = reversed.clone();
+            next.push(previous.clone());
+            self.collect_paths(next, paths);
+        }
+    }
+}
+
+pub fn reconstruct_path<N: Clone + Eq + Hash>(parents: &HashMap<N, N>, goal: &N) -> Vec<N> {
+    let mut path = vec![goal.clone()];
+    while let Some(parent) = parents.get(path.last().unwrap()) {
+        path.push(parent.clone());
+    }
+    path.reverse();
+    path
+}
+
+#[allow(dead_code)]
+pub fn bfs<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, usize>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut tree = SearchTree {
+        distances: HashMap::from([(start.clone(), 0)]),
+        parents: HashMap::new(),
+    };
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            return tree.path_to(&node);
+        }
+        let distance = tree.distances[&node] + 1;
+        for next in successors(&node) {
+            if !tree.distances.contains_key(&next) {
+                tree.distances.insert(next.clone(), distance);
+                tree.parents.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+#[allow(dead_code)]
+pub fn bfs_all<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> SearchTree<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut tree = SearchTree {
+        distances: HashMap::from([(start.clone(), 0)]),
+        parents: HashMap::new(),
+    };
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        let distance = tree.distances[&node] + 1;
+        for next in successors(&node) {
+            if !tree.distances.contains_key(&next) {
+                tree.distances.insert(next.clone(), distance);
+                tree.parents.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+    tree
+}
+
+#[allow(dead_code)]
+pub fn dijkstra<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    a_star(start, successors, |_| C::default(), is_goal)
+}
+
+#[allow(dead_code)]
+pub fn dijkstra_all<N, C, I>(start: N, successors: impl FnMut(&N) -> I) -> SearchTree<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    explore(start, successors, |_| C::default(), |_| false).0
+}
+
+pub fn a_star<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> C,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (tree, goal) = explore(start, successors, heuristic, is_goal);
+    goal.and_then(|goal| tree.path_to(&goal))
+}
+
+pub fn all_shortest_paths<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<ShortestPaths<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut nodes = vec![start.clone()];
+    let mut distances = HashMap::from([(start, C::default())]);
+    let mut predecessors: HashMap<N, Vec<N>> = HashMap::new();
+    let mut queue = BinaryHeap::from([Reverse((C::default(), 0))]);
+    let mut goals = Vec::new();
+    let mut best: Option<C> = None;
+
+    while let Some(Reverse((cost, index))) = queue.pop() {
+        if best.is_some_and(|best| cost > best) {
+            break;
+        }
+        let node = nodes[index].clone();
+        if distances[&node] < cost {
+            continue;
+        }
+        if is_goal(&node) {
+            best = Some(cost);
+            goals.push(node);
+            continue;
+        }
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+            match distances.get(&next) {
+                Some(&known) if known < next_cost => {}
+                Some(&known) if known == next_cost => {
+                    predecessors.entry(next).or_default().push(node.clone());
+                }
+                _ => {
+                    distances.insert(next.clone(), next_cost);
+                    predecessors.insert(next.clone(), vec![node.clone()]);
+                    nodes.push(next);
+                    queue.push(Reverse((next_cost, nodes.len() - 1)));
+                }
+            }
+        }
+    }
+
+    best.map(|cost| ShortestPaths {
+        cost,
+        goals,
+        predecessors,
+    })
+}
+
+fn explore<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> (SearchTree<N, C>, Option<N>)
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut tree = SearchTree {
+        distances: HashMap::from([(start.clone(), C::default())]),
+        parents: HashMap::new(),
+    };
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+    let mut nodes = vec![start];
+
+    while let Some(Reverse((_, cost, index))) = queue.pop() {
+        let node = nodes[index].clone();
+        if tree.distances[&node] < cost {
+            continue;
+        }
+        if is_goal(&node) {
+            return (tree, Some(node));
+        }
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+            if tree
+                .distances
+                .get(&next)
+                .is_none_or(|&known| next_cost < known)
+            {
+                tree.distances.insert(next.clone(), next_cost);
+                tree.parents.insert(next.clone(), node.clone());
+                let priority = next_cost + heuristic(&next);
+                nodes.push(next);
+                queue.push(Reverse((priority, next_cost, nodes.len() - 1)));
+            }
+        }
+    }
+
+    (tree, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph() -> HashMap<char, Vec<(char, u32)>> {
+        HashMap::from([
+            ('a', vec![('b', 1), ('c', 4)]),
+            ('b', vec![('c', 1), ('d', 5)]),
+            ('c', vec![('d', 1)]),
+            ('d', vec![]),
+        ])
+    }
+
+    #[test]
+    fn test_dijkstra_and_a_star() {
+        let graph = graph();
+        let path = dijkstra('a', |n| graph[n].clone(), |n| *n == 'd').unwrap();
+        assert_eq!(path.nodes, vec!['a', 'b', 'c', 'd']);
+        assert_eq!(path.cost, 3);
+
+        let path = a_star(
+            'a',
+            |n| graph[n].clone(),
+            |n| ('d' as u32) - (*n as u32),
+            |n| *n == 'd',
+        )
+        .unwrap();
+        assert_eq!(path.cost, 3);
+        assert!(dijkstra('d', |n| graph[n].clone(), |n| *n == 'a').is_none());
+    }
+
+    #[test]
+    fn test_bfs_on_line() {
+        let successors = |n: &i32| [n - 1, n + 1].into_iter().filter(|n| (0..10).contains(n));
+        let path = bfs(2, successors, |n| *n == 7).unwrap();
+        assert_eq!(path.nodes, vec![2, 3, 4, 5, 6, 7]);
+        assert_eq!(path.cost, 5);
+
+        let tree = bfs_all(0, successors);
+        assert_eq!(tree.distance(&9), Some(9));
+        assert_eq!(tree.path_to(&3).unwrap().nodes, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_dijkstra_all_distances() {
+        let graph = graph();
+        let tree = dijkstra_all('a', |n| graph[n].clone());
+        assert_eq!(tree.distance(&'c'), Some(2));
+        assert_eq!(tree.distance(&'d'), Some(3));
+        assert_eq!(tree.path_to(&'c').unwrap().nodes, vec!['a', 'b', 'c']);
+    }
+
+    #[test]
+    fn test_all_shortest_paths() {
+        // diamond: a -> b -> d and a -> c -> d with equal cost, plus a longer e detour
+        let graph = HashMap::from([
+            ('a', vec![('b', 1), ('c', 1), ('e', 1)]),
+            ('b', vec![('d', 1)]),
+            ('c', vec![('d', 1)]),
+            ('e', vec![('d', 5)]),
+            ('d', vec![]),
+        ]);
+        let paths = all_shortest_paths('a', |n| graph[n].clone(), |n| *n == 'd').unwrap();
+        assert_eq!(paths.cost, 2);
+        assert_eq!(paths.nodes_on_paths(), HashSet::from(['a', 'b', 'c', 'd']));
+        let mut all = paths.paths();
+        all.sort();
+        assert_eq!(all, vec![vec!['a', 'b', 'd'], vec!['a', 'c', 'd']]);
+    }
+}
@@ -0,0 +1,267 @@
+use crate::common::grid_cell::GridCell;
+use crate::common::models::Grid;
+use crate::common::parse_error::{parse_str, ParseError};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+const SEPARATOR: &str = "---";
+
+// "key: value" header lines, a separator, then the rendered body; stable enough to store and diff
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Snapshot {
+    header: Vec<(String, String)>,
+    body: String,
+}
+
+#[allow(dead_code)]
+impl Snapshot {
+    // drops the leading newline the grid displays start with and records the body size
+    pub fn new(body: impl Display) -> Self {
+        let rendered = body.to_string();
+        let body = normalize(rendered.strip_prefix('\n').unwrap_or(&rendered));
+        let len_x = body.lines().map(|line| line.chars().count()).max();
+        let len_y = body.lines().count();
+        Self {
+            header: Vec::new(),
+            body,
+        }
+        .with("size", format!("{}x{}", len_x.unwrap_or(0), len_y))
+    }
+    pub fn with(mut self, key: &str, value: impl Display) -> Self {
+        let value = value.to_string();
+        match self.header.iter_mut().find(|(k, _)| k == key) {
+            Some(entry) => entry.1 = value,
+            None => self.header.push((key.to_string(), value)),
+        }
+        self
+    }
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.header
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+    pub fn parse<T>(&self, key: &str) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let value = self.get(key).ok_or_else(|| {
+            ParseError::missing(&self.to_string(), &format!("a '{}' header", key))
+        })?;
+        let line = format!("{}: {}", key, value);
+        parse_str(&line, &line[key.len() + 2..])
+    }
+    pub fn header(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.header.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+}
+
+// line endings are normalized so stored files survive a CRLF checkout
+fn normalize(body: &str) -> String {
+    body.lines()
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_end_matches('\n')
+        .to_string()
+}
+
+impl Display for Snapshot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (key, value) in &self.header {
+            writeln!(f, "{}: {}", key, value)?;
+        }
+        writeln!(f, "{}", SEPARATOR)?;
+        writeln!(f, "{}", self.body)
+    }
+}
+
+impl FromStr for Snapshot {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut header = Vec::new();
+        let mut offset = 0;
+        for line in s.split_inclusive('\n') {
+            offset += line.len();
+            let line = line.trim_end_matches(['\r', '\n']);
+            if line == SEPARATOR {
+                return Ok(Self {
+                    header,
+                    body: normalize(&s[offset..]),
+                });
+            }
+            let (key, value) = line.split_once(':').ok_or_else(|| {
+                ParseError::at_str(s, line, "expected a 'key: value' header line")
+            })?;
+            let value = value.strip_prefix(' ').unwrap_or(value);
+            header.push((key.to_string(), value.to_string()));
+        }
+        Err(ParseError::missing(
+            s,
+            &format!("a '{}' line before the body", SEPARATOR),
+        ))
+    }
+}
+
+#[allow(dead_code)]
+impl<T: GridCell> Grid<T> {
+    // the legend lists every character in order of first appearance, with its name if known
+    pub fn to_snapshot(&self) -> Snapshot {
+        let mut legend: Vec<(char, Option<&str>)> = Vec::new();
+        for (_, cell) in self.iter() {
+            let c = cell.to_char();
+            if !legend.iter().any(|(seen, _)| *seen == c) {
+                legend.push((c, cell.name()));
+            }
+        }
+        let legend = legend
+            .iter()
+            .map(|(c, name)| match name {
+                Some(name) => format!("{}={}", c, name),
+                None => c.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        Snapshot::new(self.to_text()).with("legend", legend)
+    }
+}
+
+#[allow(dead_code)]
+impl<T: GridCell + Default> Grid<T> {
+    pub fn from_snapshot(snapshot: &Snapshot) -> Result<Self, ParseError> {
+        let grid: Grid<T> = snapshot.body().parse()?;
+        let size = format!("{}x{}", grid.len_x(), grid.len_y());
+        match snapshot.get("size") {
+            Some(expected) if expected != size => Err(ParseError::at(
+                snapshot.body(),
+                0,
+                format!("grid is {} but the header says {}", size, expected),
+            )),
+            _ => Ok(grid),
+        }
+    }
+}
+
+// compares against snapshots/<name>.snap; APP_UPDATE_SNAPSHOTS=1 writes the file instead
+#[cfg(test)]
+pub fn assert_snapshot(name: &str, actual: &Snapshot) {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("snapshots")
+        .join(format!("{}.snap", name));
+    if std::env::var_os("APP_UPDATE_SNAPSHOTS").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, actual.to_string()).unwrap();
+        return;
+    }
+    let Ok(stored) = std::fs::read_to_string(&path) else {
+        panic!(
+            "no snapshot at {}, rerun with APP_UPDATE_SNAPSHOTS=1 to create it:\n{}",
+            path.display(),
+            actual
+        );
+    };
+    let expected: Snapshot = stored
+        .parse()
+        .unwrap_or_else(|e| panic!("{} is not a valid snapshot: {:?}", path.display(), e));
+    if expected == *actual {
+        return;
+    }
+    let (expected, actual) = (expected.to_string(), actual.to_string());
+    let lines = expected.lines().count().max(actual.lines().count());
+    let (line, expected_line, actual_line) = (0..lines)
+        .map(|i| {
+            let line = |text: &str| text.lines().nth(i).unwrap_or_default().to_string();
+            (i + 1, line(&expected), line(&actual))
+        })
+        .find(|(_, e, a)| e != a)
+        .unwrap_or_default();
+    panic!(
+        "snapshot {} differs at line {}\n  stored: {}\n  actual: {}\nfull output:\n{}",
+        path.display(),
+        line,
+        expected_line,
+        actual_line,
+        actual
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::grid_cell::grid_cell;
+    use crate::common::models::{Direction, Point};
+    use std::collections::HashMap;
+
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    enum Tile {
+        #[default]
+        Open,
+        Wall,
+        Crate,
+    }
+
+    grid_cell!(Tile {
+        Open => '.',
+        Wall => '#',
+        Crate => 'O',
+    });
+
+    #[test]
+    fn test_text_round_trip() {
+        let snapshot = Snapshot::new("\nab\ncd")
+            .with("robot", Point::new(1, 2))
+            .with("facing", Direction::Left);
+        let text = snapshot.to_string();
+        assert_eq!(text, "size: 2x2\nrobot: (1, 2)\nfacing: <\n---\nab\ncd\n");
+        let parsed: Snapshot = text.replace('\n', "\r\n").parse().unwrap();
+        assert_eq!(parsed, snapshot);
+        assert_eq!(parsed.parse::<Point<usize>>("robot"), Ok(Point::new(1, 2)));
+        assert_eq!(parsed.parse::<Direction>("facing"), Ok(Direction::Left));
+        assert!(parsed.parse::<Direction>("robot").is_err());
+        assert!(parsed.parse::<usize>("moves").is_err());
+
+        let err = "size 2x2\n---\nab".parse::<Snapshot>().unwrap_err();
+        assert_eq!((err.line(), err.column()), (1, 1));
+        assert!("size: 2x2\nab".parse::<Snapshot>().is_err());
+    }
+
+    #[test]
+    fn test_grid_round_trip() {
+        let grid: Grid<Tile> = "#####\n#.O.#\n#####".parse().unwrap();
+        let snapshot = grid.to_snapshot();
+        assert_eq!(snapshot.get("size"), Some("5x3"));
+        assert_eq!(snapshot.get("legend"), Some("#=Wall .=Open O=Crate"));
+        assert_eq!(Grid::<Tile>::from_snapshot(&snapshot), Ok(grid.clone()));
+
+        let resized = snapshot.clone().with("size", "4x3");
+        assert!(Grid::<Tile>::from_snapshot(&resized).is_err());
+        let chars: Grid<char> = "ab\nba".parse().unwrap();
+        assert_eq!(chars.to_snapshot().get("legend"), Some("a b"));
+    }
+
+    #[test]
+    fn test_stored_snapshots() {
+        let grid: Grid<Tile> = "########\n#..O.O.#\n##..O..#\n#...O..#\n########"
+            .parse()
+            .unwrap();
+        assert_snapshot(
+            "tile_grid",
+            &grid
+                .to_snapshot()
+                .with("robot", Point::new(2, 2))
+                .with("facing", Direction::Right),
+        );
+
+        let path = [Point::new(1, 1), Point::new(2, 1), Point::new(2, 2)];
+        let arrow = '>';
+        let overrides: HashMap<_, _> = path.iter().map(|point| (point, &arrow)).collect();
+        assert_snapshot(
+            "tile_grid_path",
+            &Snapshot::new(grid.display_with_overrides(overrides)),
+        );
+    }
+}
@@ -0,0 +1,227 @@
+use crate::common::models::{Direction, Point};
+use crate::common::parse_error::ParseError;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+const EMPTY_CELL: char = '.';
+
+#[derive(Clone, Debug)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point<i64>, T>,
+    bounds: Option<(Point<i64>, Point<i64>)>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn with_bounds(min: Point<i64>, max: Point<i64>) -> Self {
+        let mut grid = Self::new();
+        grid.extend_bounds(&min);
+        grid.extend_bounds(&max);
+        grid
+    }
+    pub fn from_str_with<F: FnMut(char, &Point<usize>) -> Result<Option<T>, ParseError>>(
+        s: &str,
+        mut parse_item: F,
+    ) -> Result<Self, ParseError> {
+        let len_x = s
+            .lines()
+            .next()
+            .ok_or_else(|| ParseError::missing(s, "a grid"))?
+            .chars()
+            .count();
+        let mut grid = Self::new();
+        for (y, line) in s.lines().enumerate() {
+            if line.chars().count() != len_x {
+                return Err(ParseError::at_point(
+                    s,
+                    &Point { x: 0, y },
+                    format!("expected a row of length {}", len_x),
+                ));
+            }
+            for (x, c) in line.chars().enumerate() {
+                let point = Point { x, y };
+                grid.extend_bounds(&Point::new(x as i64, y as i64));
+                if let Some(item) = parse_item(c, &point)? {
+                    grid.insert(Point::new(x as i64, y as i64), item);
+                }
+            }
+        }
+        Ok(grid)
+    }
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+    pub fn min(&self) -> Option<&Point<i64>> {
+        self.bounds.as_ref().map(|(min, _)| min)
+    }
+    pub fn max(&self) -> Option<&Point<i64>> {
+        self.bounds.as_ref().map(|(_, max)| max)
+    }
+    pub fn extend_bounds(&mut self, point: &Point<i64>) {
+        match &mut self.bounds {
+            None => self.bounds = Some((*point, *point)),
+            Some((min, max)) => {
+                min.x = min.x.min(point.x);
+                min.y = min.y.min(point.y);
+                max.x = max.x.max(point.x);
+                max.y = max.y.max(point.y);
+            }
+        }
+    }
+    pub fn in_bounds(&self, point: &Point<i64>) -> bool {
+        self.bounds.as_ref().is_some_and(|(min, max)| {
+            (min.x..=max.x).contains(&point.x) && (min.y..=max.y).contains(&point.y)
+        })
+    }
+    pub fn get(&self, point: &Point<i64>) -> Option<&T> {
+        self.cells.get(point)
+    }
+    pub fn get_mut(&mut self, point: &Point<i64>) -> Option<&mut T> {
+        self.cells.get_mut(point)
+    }
+    pub fn contains(&self, point: &Point<i64>) -> bool {
+        self.cells.contains_key(point)
+    }
+    pub fn insert(&mut self, point: Point<i64>, value: T) -> Option<T> {
+        self.extend_bounds(&point);
+        self.cells.insert(point, value)
+    }
+    pub fn remove(&mut self, point: &Point<i64>) -> Option<T> {
+        self.cells.remove(point)
+    }
+    pub fn four_way_neighbors(&self, point: &Point<i64>) -> [Point<i64>; 4] {
+        Direction::directions().map(|direction| point + &direction.offset())
+    }
+    pub fn eight_way_neighbors(&self, point: &Point<i64>) -> Vec<Point<i64>> {
+        (-1..=1)
+            .flat_map(|y| (-1..=1).map(move |x| Point::new(x, y)))
+            .filter(|offset| offset != &Point::new(0, 0))
+            .map(|offset| point + &offset)
+            .collect()
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (&Point<i64>, &T)> {
+        let mut cells: Vec<_> = self.cells.iter().collect();
+        cells.sort_by_key(|(point, _)| (point.y, point.x));
+        cells.into_iter()
+    }
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.values()
+    }
+}
+
+impl<T: Display> Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            SparseGridDisplay {
+                grid: self,
+                overrides: HashMap::<&Point<i64>, &char>::default()
+            }
+        )
+    }
+}
+
+impl<T: Display> SparseGrid<T> {
+    #[allow(dead_code)]
+    pub fn display_with_overrides<'a, V: Display>(
+        &'a self,
+        overrides: HashMap<&'a Point<i64>, &'a V>,
+    ) -> SparseGridDisplay<'a, T, V> {
+        SparseGridDisplay {
+            grid: self,
+            overrides,
+        }
+    }
+}
+
+pub struct SparseGridDisplay<'a, T, V> {
+    grid: &'a SparseGrid<T>,
+    overrides: HashMap<&'a Point<i64>, &'a V>,
+}
+
+impl<T: Display, V: Display> Display for SparseGridDisplay<'_, T, V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Some((min, max)) = &self.grid.bounds else {
+            return Ok(());
+        };
+        for y in min.y..=max.y {
+            writeln!(f)?;
+            for x in min.x..=max.x {
+                let point = Point { x, y };
+                if let Some(o) = self.overrides.get(&point) {
+                    write!(f, "{}", o)?;
+                } else if let Some(value) = self.grid.get(&point) {
+                    write!(f, "{}", value)?;
+                } else {
+                    write!(f, "{}", EMPTY_CELL)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounds_grow_with_negative_points() {
+        let mut grid = SparseGrid::new();
+        assert!(!grid.in_bounds(&Point::new(0, 0)));
+        grid.insert(Point::new(2, 1), 'a');
+        grid.insert(Point::new(-1, -2), 'b');
+        assert_eq!(grid.min(), Some(&Point::new(-1, -2)));
+        assert_eq!(grid.max(), Some(&Point::new(2, 1)));
+        assert!(grid.in_bounds(&Point::new(0, 0)));
+        assert!(!grid.in_bounds(&Point::new(3, 0)));
+        assert_eq!(grid.to_string(), "\nb...\n....\n....\n...a");
+    }
+
+    #[test]
+    fn test_row_major_iteration_and_neighbors() {
+        let mut grid = SparseGrid::new();
+        for (i, point) in [(5, 0), (-3, 1), (0, 0), (1, -4)].into_iter().enumerate() {
+            grid.insert(Point::new(point.0, point.1), i);
+        }
+        assert_eq!(
+            grid.iter().map(|(_, &v)| v).collect::<Vec<_>>(),
+            vec![3, 2, 0, 1]
+        );
+        assert!(grid
+            .four_way_neighbors(&Point::new(0, 0))
+            .contains(&Point::new(0, -1)));
+        assert_eq!(grid.eight_way_neighbors(&Point::new(0, 0)).len(), 8);
+    }
+
+    #[test]
+    fn test_parse_and_overrides() {
+        let input = "..#\n#..";
+        let grid = SparseGrid::from_str_with(input, |c, _| Ok((c == '#').then_some(c))).unwrap();
+        assert_eq!(grid.len(), 2);
+        assert_eq!(grid.max(), Some(&Point::new(2, 1)));
+        let guard = Point::new(1, 1);
+        assert_eq!(
+            grid.display_with_overrides([(&guard, &'^')].into_iter().collect())
+                .to_string(),
+            "\n..#\n#^."
+        );
+        assert!(SparseGrid::<char>::from_str_with("..\n.", |_, _| Ok(None)).is_err());
+    }
+}
@@ -0,0 +1,160 @@
+use crate::common::image::Rgb;
+use crate::common::models::{Grid, Point};
+use colored::{ColoredString, Colorize};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+#[derive(Copy, Clone, Default, Hash, PartialEq, Eq, Debug)]
+pub struct Style {
+    pub foreground: Option<Rgb>,
+    pub background: Option<Rgb>,
+    pub bold: bool,
+}
+
+#[allow(dead_code)]
+impl Style {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn foreground(mut self, color: Rgb) -> Self {
+        self.foreground = Some(color);
+        self
+    }
+    pub fn background(mut self, color: Rgb) -> Self {
+        self.background = Some(color);
+        self
+    }
+    pub fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+    pub fn paint(&self, text: impl Display) -> String {
+        self.colored(text).to_string()
+    }
+    // the escapes are only rendered when `colored` decides stdout should be colorized
+    pub fn colored(&self, text: impl Display) -> ColoredString {
+        let mut text = text.to_string().normal();
+        if let Some(Rgb { r, g, b }) = self.foreground {
+            text = text.truecolor(r, g, b);
+        }
+        if let Some(Rgb { r, g, b }) = self.background {
+            text = text.on_truecolor(r, g, b);
+        }
+        if self.bold {
+            text = text.bold();
+        }
+        text
+    }
+}
+
+#[allow(dead_code)]
+impl<T: Display> Grid<T> {
+    #[allow(clippy::type_complexity)]
+    pub fn styled<'a>(
+        &'a self,
+        style: impl Fn(&Point<usize>, &T) -> Style + 'a,
+    ) -> StyledGridDisplay<'a, T, char, impl Fn(&Point<usize>, &T, Option<&char>) -> Style + 'a>
+    {
+        self.styled_with_overrides(HashMap::new(), move |point, value, _| style(point, value))
+    }
+    pub fn styled_with_overrides<'a, V: Display, F: Fn(&Point<usize>, &T, Option<&V>) -> Style>(
+        &'a self,
+        overrides: HashMap<&'a Point<usize>, &'a V>,
+        style: F,
+    ) -> StyledGridDisplay<'a, T, V, F> {
+        StyledGridDisplay {
+            grid: self,
+            overrides,
+            style,
+        }
+    }
+}
+
+pub struct StyledGridDisplay<'a, T, V, F> {
+    grid: &'a Grid<T>,
+    overrides: HashMap<&'a Point<usize>, &'a V>,
+    style: F,
+}
+
+// colors are dropped by `colored` when stdout is not a terminal or NO_COLOR is set
+impl<T, V, F> Display for StyledGridDisplay<'_, T, V, F>
+where
+    T: Display,
+    V: Display,
+    F: Fn(&Point<usize>, &T, Option<&V>) -> Style,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let colorize = colored::control::SHOULD_COLORIZE.should_colorize();
+        for y in 0..self.grid.len_y() {
+            writeln!(f)?;
+            for x in 0..self.grid.len_x() {
+                let point = Point { x, y };
+                let value = &self.grid[&point];
+                let over = self.overrides.get(&point).copied();
+                let text = match over {
+                    Some(o) => o.to_string(),
+                    None => value.to_string(),
+                };
+                if colorize {
+                    write!(f, "{}", (self.style)(&point, value, over).paint(text))?;
+                } else {
+                    write!(f, "{}", text)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use colored::{Color, Styles};
+
+    fn grid() -> Grid<char> {
+        Grid::from_iter(["#.#", "..#"].into_iter().map(|row| row.chars()))
+    }
+
+    fn wall_style(_: &Point<usize>, c: &char, over: Option<&char>) -> Style {
+        match (c, over) {
+            (_, Some(_)) => Style::new().foreground(Rgb::YELLOW).bold(),
+            ('#', None) => Style::new().background(Rgb::GRAY),
+            _ => Style::new(),
+        }
+    }
+
+    #[test]
+    fn test_style_colors() {
+        let Rgb { r, g, b } = Rgb::YELLOW;
+        let text = Style::new().foreground(Rgb::YELLOW).bold().colored("O");
+        assert_eq!(text.fgcolor(), Some(Color::TrueColor { r, g, b }));
+        assert_eq!(text.bgcolor(), None);
+        assert!(text.style().contains(Styles::Bold));
+
+        let Rgb { r, g, b } = Rgb::GRAY;
+        let text = Style::new().background(Rgb::GRAY).colored("#");
+        assert_eq!(text.bgcolor(), Some(Color::TrueColor { r, g, b }));
+        assert!(!text.style().contains(Styles::Bold));
+        assert!(Style::new().colored(".").is_plain());
+    }
+
+    #[test]
+    fn test_styled_display() {
+        let grid = grid();
+        let path = Point::new(1, 1);
+        let overrides: HashMap<_, _> = [(&path, &'O')].into_iter().collect();
+
+        // whether escapes are written depends on the terminal, so compare the text without them
+        let escapes = regex::Regex::new("\x1b\\[[0-9;]*m").unwrap();
+        let styled = grid
+            .styled_with_overrides(overrides.clone(), wall_style)
+            .to_string();
+        assert_eq!(escapes.replace_all(&styled, ""), "\n#.#\n.O#");
+        assert_eq!(
+            escapes.replace_all(&styled, ""),
+            grid.display_with_overrides(overrides).to_string()
+        );
+        let unstyled = grid.styled(|_, _| Style::new()).to_string();
+        assert_eq!(unstyled, "\n#.#\n..#");
+    }
+}
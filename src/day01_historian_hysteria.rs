@@ -10,9 +10,11 @@ pub fn run(context: &mut Context) {
     let distance = get_distance(&left, &right);
 
     println!("Distance: {}", distance);
+    context.set_part_1_answer(distance);
 
     let similarity_score = get_similarity_score(&left, &right);
     println!("Similarity score: {}", similarity_score);
+    context.set_part_2_answer(similarity_score);
 }
 
 fn parse(input: &str) -> (Vec<i32>, Vec<i32>) {
@@ -1,4 +1,5 @@
-﻿use crate::common::{Context, InputProvider};
+﻿use crate::common::parse_error::{parse_str, ParseError};
+use crate::common::{Context, InputProvider};
 use std::str::FromStr;
 
 pub fn run(context: &mut Context) {
@@ -12,12 +13,14 @@ pub fn run(context: &mut Context) {
         .filter(|&line| line.parse::<Report>().unwrap().is_safe())
         .count();
     println!("Safe reports: {}", safe_count);
+    context.set_part_1_answer(safe_count);
 
     let safe_count = input
         .lines()
         .filter(|&line| line.parse::<Report>().unwrap().is_safe_v2())
         .count();
     println!("Safe reports v2: {}", safe_count);
+    context.set_part_2_answer(safe_count);
 }
 
 struct Report(Vec<i32>);
@@ -64,12 +67,12 @@ impl Report {
 }
 
 impl FromStr for Report {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(Self(
             s.split_whitespace()
-                .map(|c| c.parse())
+                .map(|c| parse_str(s, c))
                 .collect::<Result<Vec<i32>, _>>()?,
         ))
     }
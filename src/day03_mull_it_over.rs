@@ -10,9 +10,11 @@ pub fn run(context: &mut Context) {
     let operations = parse_operations(input);
     let sum: i32 = operations.iter().map(|o| o.product()).sum();
     println!("Total sum of products: {}", sum);
+    context.set_part_1_answer(sum);
 
     let product = calculate_sum_products(input);
     println!("Product v2: {}", product);
+    context.set_part_2_answer(product);
 }
 
 fn parse_operations(input: &str) -> Vec<MultiplicationOperation> {
@@ -1,4 +1,6 @@
-﻿use crate::common::{Context, InputProvider};
+﻿use crate::common::parse;
+use crate::common::parse_error::{parse_str, ParseError};
+use crate::common::{Context, InputProvider};
 use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
 
@@ -8,12 +10,14 @@ pub fn run(context: &mut Context) {
     let input = context.get_input();
     let input = input.as_str();
 
-    let input_parts = input.split_once("\n\n").unwrap();
-    let rules = OrderRules::from_str(input_parts.0).unwrap();
+    let [rules, updates] = parse::sections_n(input).unwrap();
+    let rules = OrderRules::from_str(rules).unwrap();
 
-    let (sum, sum2) = solve(&rules, input_parts.1);
+    let (sum, sum2) = solve(&rules, updates);
     println!("the sum of the middle pages is {}", sum);
     println!("the sum of the corrected middle pages is {}", sum2);
+    context.set_part_1_answer(sum);
+    context.set_part_2_answer(sum2);
 }
 
 fn solve(rules: &OrderRules, input: &str) -> (u32, u32) {
@@ -75,25 +79,18 @@ struct OrderRules {
 }
 
 impl FromStr for OrderRules {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let rules = s
-            .lines()
-            .filter_map(|line| {
-                let mut parts = line.split('|');
-                if let Some(left) = parts.next() {
-                    if let Ok(left) = left.parse::<u32>() {
-                        let right = parts.next().unwrap().parse::<u32>().unwrap();
-                        return Some((left, right));
-                    }
-                }
-                None
-            })
-            .fold(HashMap::new(), |mut acc, (left, right)| {
-                acc.entry(left).or_insert_with(Vec::new).push(right);
-                acc
-            });
+        let mut rules = HashMap::new();
+        for line in s.lines() {
+            let (left, right) = line
+                .split_once('|')
+                .ok_or_else(|| ParseError::at_str(s, line, "expected a rule like '47|53'"))?;
+            let left: u32 = parse_str(s, left)?;
+            let right: u32 = parse_str(s, right)?;
+            rules.entry(left).or_insert_with(Vec::new).push(right);
+        }
         Ok(OrderRules { rules })
     }
 }
@@ -1,6 +1,7 @@
 ﻿use crate::common::models::{Direction, Point};
 use crate::common::{Context, InputProvider};
-use anyhow::Context as AnyhowContext;
+use crate::common::parse_error::ParseError;
+use crate::common::sparse_grid::SparseGrid;
 use std::collections::HashSet;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
@@ -11,33 +12,49 @@ pub fn run(context: &mut Context) {
     let input = context.get_input();
     let input = input.as_str();
 
-    let visited = solve(input);
+    let visited = solve(context, input);
     println!("the guard visited {} places", visited);
+    context.set_part_1_answer(visited);
 
     let obstacles = solve_v2(input);
     println!("obstacles can be placed in {} places", obstacles);
+    context.set_part_2_answer(obstacles);
 }
 
-fn solve(input: &str) -> usize {
+fn solve(context: &Context, input: &str) -> usize {
     let mut map: Map = input.parse().unwrap();
     log::debug!("{}", map);
-    while map.next().unwrap() {}
+    let mut playback = context.playback();
+    loop {
+        if let Some(playback) = &mut playback {
+            playback.push_frame(&map);
+        }
+        if !map.next().unwrap() {
+            break;
+        }
+    }
     log::debug!("guard exited at location: {:?}", map.guard.position);
     log::debug!("{}", map);
-    map.visited.len()
+    if let Some(playback) = playback {
+        playback.run().unwrap();
+    }
+    map.visited_count()
 }
 
 fn solve_v2(input: &str) -> usize {
     let ref_map: Map = input.parse().unwrap();
     let mut map = ref_map.clone();
     while map.next().unwrap() { }
-    let mut new_obstacles = map.visited;
-    new_obstacles.remove(&ref_map.guard.position);
+    let new_obstacles = map
+        .tiles
+        .iter()
+        .filter(|(point, tile)| matches!(tile, Tile::Visited) && **point != ref_map.guard.position)
+        .map(|(point, _)| *point);
     
     let mut result = 0usize;
     for obstacle in new_obstacles {
         let mut map = ref_map.clone();
-        map.obstacles.insert(obstacle);
+        map.tiles.insert(obstacle, Tile::Obstacle);
         if map.does_loop().unwrap() {
             result += 1;
         }
@@ -48,21 +65,24 @@ fn solve_v2(input: &str) -> usize {
 
 #[derive(Clone)]
 struct Map {
-    size_x: usize,
-    size_y: usize,
     guard: GuardPosition,
-    obstacles: HashSet<Point<usize>>,
-    visited: HashSet<Point<usize>>,
+    tiles: SparseGrid<Tile>,
+}
+
+#[derive(Clone, Copy)]
+enum Tile {
+    Obstacle,
+    Visited,
 }
 
 #[derive(Clone, Hash, PartialEq, Eq, Ord, PartialOrd, Debug)]
 struct GuardPosition {
-    position: Point<usize>,
+    position: Point<i64>,
     direction: Direction,
 }
 
 impl GuardPosition {
-    pub fn new(position: Point<usize>, direction: Direction) -> Self {
+    pub fn new(position: Point<i64>, direction: Direction) -> Self {
         Self {
             position,
             direction,
@@ -70,54 +90,43 @@ impl GuardPosition {
     }
 }
 
+impl Display for Tile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Tile::Obstacle => write!(f, "#"),
+            Tile::Visited => write!(f, "X"),
+        }
+    }
+}
 
 impl Display for Map {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for y in 0..self.size_y {
-            write!(f, "\n")?;
-            for x in 0..self.size_x {
-                let point = Point { x, y };
-                if self.obstacles.contains(&point) {
-                    write!(f, "#")?;
-                } else if self.guard.position == point {
-                    write!(
-                        f,
-                        "{}",
-                        match self.guard.direction {
-                            Direction::Up => "^",
-                            Direction::Down => "v",
-                            Direction::Left => "<",
-                            Direction::Right => ">",
-                        }
-                    )?;
-                } else if self.visited.contains(&point) {
-                    write!(f, "X")?;
-                } else {
-                    write!(f, ".")?;
-                }
-            }
-        }
-        Ok(())
+        write!(
+            f,
+            "{}",
+            self.tiles.display_with_overrides(
+                [(&self.guard.position, &self.guard.direction)]
+                    .into_iter()
+                    .collect()
+            )
+        )
     }
 }
 
 impl Map {
-    pub fn change_direction_and_get_next(&mut self) -> anyhow::Result<Option<Point<usize>>> {
+    pub fn change_direction_and_get_next(&mut self) -> anyhow::Result<Option<Point<i64>>> {
         let mut i = 0;
         let next = loop {
             if i >= 4 {
                 return Err(anyhow::anyhow!("guard is stuck turning!!"));
             }
-            if let Some(next) = self.guard.position.move_to(self.guard.direction) {
-                if next.x >= self.size_x || next.y >= self.size_y {
-                    return Ok(None); // left map
-                }
-                if !self.obstacles.contains(&next) {
-                    break next;
-                }
-            } else {
+            let next = self.guard.position + self.guard.direction.offset();
+            if !self.tiles.in_bounds(&next) {
                 return Ok(None); // left map
             }
+            if !matches!(self.tiles.get(&next), Some(Tile::Obstacle)) {
+                break next;
+            }
 
             if i == 0 {
                 log::trace!("guard hit obstacle at location: {:?}", self.guard.position);
@@ -130,10 +139,16 @@ impl Map {
 
         Ok(Some(next))
     }
-    fn walk_to(&mut self, next: Point<usize>) {
-        self.visited.insert(next.clone());
+    fn walk_to(&mut self, next: Point<i64>) {
+        self.tiles.insert(next, Tile::Visited);
         self.guard.position = next;
     }
+    pub fn visited_count(&self) -> usize {
+        self.tiles
+            .values()
+            .filter(|tile| matches!(tile, Tile::Visited))
+            .count()
+    }
     pub fn next(&mut self) -> anyhow::Result<bool> {
         if let Some(next) = self.change_direction_and_get_next()? {
             self.walk_to(next);
@@ -156,76 +171,24 @@ impl Map {
 }
 
 impl FromStr for Map {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut size_x: Option<usize> = None;
-        let mut size_y = 0usize;
-        let mut obstacles = HashSet::new();
-        let mut guard_position = None;
-        let mut guard_heading = None;
-
-        for line in s.lines() {
-            let mut x = 0usize;
-            for c in line.chars() {
-                let point = Point { x, y: size_y };
-                match c {
-                    '#' => {
-                        obstacles.insert(point);
-                    }
-                    'v' => {
-                        guard_position = Some(point);
-                        guard_heading = Some(Direction::Down);
-                    }
-                    '>' => {
-                        guard_position = Some(point);
-                        guard_heading = Some(Direction::Right);
-                    }
-                    '^' => {
-                        guard_position = Some(point);
-                        guard_heading = Some(Direction::Up);
-                    }
-                    '<' => {
-                        guard_position = Some(point);
-                        guard_heading = Some(Direction::Left);
-                    }
-                    '.' => {}
-                    other => {
-                        return Err(anyhow::anyhow!(
-                            "Unexpected character {} in line {}",
-                            other,
-                            size_y
-                        ))
-                    }
-                }
-                x += 1;
+        let mut guard = None;
+        let mut tiles = SparseGrid::from_str_with(s, |c, point| match c {
+            '#' => Ok(Some(Tile::Obstacle)),
+            '.' => Ok(None),
+            '^' | 'v' | '<' | '>' => {
+                let position = Point::new(point.x as i64, point.y as i64);
+                guard = Some(GuardPosition::new(position, c.try_into().unwrap()));
+                Ok(None)
             }
+            other => Err(ParseError::invalid_char(s, point, other)),
+        })?;
+        let guard = guard.ok_or_else(|| ParseError::missing(s, "a guard"))?;
+        tiles.insert(guard.position, Tile::Visited);
 
-            if let Some(size_x) = size_x {
-                if x != size_x {
-                    return Err(anyhow::anyhow!(
-                        "Expected constant line length of {} but got {} in line {}",
-                        size_x,
-                        x,
-                        size_y
-                    ));
-                }
-            } else {
-                size_x = Some(x);
-            }
-            size_y += 1;
-        }
-
-        let guard_position = guard_position.context("guard not found")?;
-        let visited = HashSet::from([guard_position.clone()]);
-
-        Ok(Map {
-            size_x: size_x.context("invalid size x")?,
-            size_y,
-            guard: GuardPosition::new(guard_position, guard_heading.context("guard not found")?),
-            obstacles,
-            visited,
-        })
+        Ok(Map { guard, tiles })
     }
 }
 
@@ -1,5 +1,5 @@
-﻿use crate::common::{Context, InputProvider};
-use anyhow::Context as AnyhowContext;
+﻿use crate::common::parse_error::{parse_str, ParseError};
+use crate::common::{Context, InputProvider};
 use std::str::FromStr;
 
 pub fn run(context: &mut Context) {
@@ -10,8 +10,10 @@ pub fn run(context: &mut Context) {
 
     let value1 = solve(input);
     println!("total calibration result: {}", value1);
+    context.set_part_1_answer(value1);
     let value2 = solve2(input);
     println!("total calibration result v2: {}", value2);
+    context.set_part_2_answer(value2);
 }
 
 fn solve(input: &str) -> i64 {
@@ -48,18 +50,21 @@ struct Operation {
 }
 
 impl FromStr for Operation {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut parts = s.split(':');
-        let next = parts.next().context("empty test value string")?;
-        let test_value = next
-            .parse()
-            .with_context(|| format!("invalid test value string: {}", next))?;
-        let parts = parts.next().context("empty values string")?.trim();
+        let next = parts
+            .next()
+            .ok_or_else(|| ParseError::missing(s, "a test value"))?;
+        let test_value = parse_str(s, next)?;
+        let parts = parts
+            .next()
+            .ok_or_else(|| ParseError::missing(s, "':' followed by values"))?
+            .trim();
         let values: Vec<i64> = parts
             .split_whitespace()
-            .map(|v| v.parse())
+            .map(|v| parse_str(s, v))
             .collect::<Result<Vec<_>, _>>()?;
         Ok(Self {
             test_value,
@@ -1,6 +1,8 @@
-﻿use crate::common::models::{MultiplyByI64, Point};
+﻿use crate::common::line_of_sight::Line;
+use crate::common::models::Point;
+use crate::common::parse_error::ParseError;
+use crate::common::sparse_grid::SparseGrid;
 use crate::common::{Context, InputProvider};
-use anyhow::Context as AnyhowContext;
 use std::collections::{HashMap, HashSet};
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
@@ -13,8 +15,10 @@ pub fn run(context: &mut Context) {
 
     let anti_nodes = count_anti_nodes(input, false);
     println!("Part 1: {}", anti_nodes);
+    context.set_part_1_answer(anti_nodes);
     let anti_nodes = count_anti_nodes(input, true);
     println!("Part 2: {}", anti_nodes);
+    context.set_part_2_answer(anti_nodes);
 }
 
 fn count_anti_nodes(input: &str, is_v2: bool) -> usize {
@@ -39,112 +43,65 @@ fn count_anti_nodes(input: &str, is_v2: bool) -> usize {
 struct Grid {
     antennas: HashMap<char, Vec<Point<i64>>>,
     anti_nodes: HashMap<char, Vec<Point<i64>>>,
-    size_x: usize,
-    size_y: usize,
+    map: SparseGrid<char>,
 }
 
 impl Display for Grid {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for y in 0..self.size_y {
-            write!(f, "\n")?;
-            'x: for x in 0..self.size_x {
-                let point = Point {
-                    x: x as i64,
-                    y: y as i64,
-                };
-                for (&c, antennas) in &self.antennas {
-                    if antennas.iter().any(|a| a == &point) {
-                        write!(f, "{}", c)?;
-                        continue 'x;
-                    }
-                }
-                if self
-                    .anti_nodes
-                    .iter()
-                    .any(|n| n.1.iter().any(|p| p == &point))
-                {
-                    write!(f, "#")?;
-                }
-
-                write!(f, ".")?;
-            }
-        }
-        Ok(())
+        let anti_nodes = self
+            .anti_nodes
+            .values()
+            .flatten()
+            .filter(|p| !self.map.contains(p))
+            .map(|p| (p, &'#'))
+            .collect();
+        write!(f, "{}", self.map.display_with_overrides(anti_nodes))
     }
 }
 
 impl Grid {
     fn in_grid(&self, point: &Point<i64>) -> bool {
-        point.x >= 0 && point.y >= 0 && point.x < self.size_x as i64 && point.y < self.size_y as i64
+        self.map.in_bounds(point)
     }
-    pub fn calculate_anti_nodes(&mut self) {
-        let mut anti_nodes = HashMap::new();
-        for (&char, antennas) in self.antennas.iter() {
-            for i in 0..antennas.len() {
-                for j in i + 1..antennas.len() {
+    fn antenna_pairs(&self) -> impl Iterator<Item = (char, &Point<i64>, &Point<i64>)> + '_ {
+        self.antennas.iter().flat_map(|(&char, antennas)| {
+            antennas.iter().enumerate().flat_map(move |(i, a)| {
+                antennas[i + 1..].iter().map(move |b| {
                     log::debug!(
                         "antenna i: {:?}, antenna j: {:?}, diff: {:?}",
-                        antennas[i],
-                        antennas[j],
-                        &antennas[i] - &antennas[j]
+                        a,
+                        b,
+                        *a - *b
                     );
-                    let diff = &antennas[i] - &antennas[j];
-
-                    let point = &antennas[i] + &diff;
-                    if self.in_grid(&point) {
-                        anti_nodes.entry(char).or_insert_with(Vec::new).push(point);
-                    }
-
-                    let point = &antennas[j] - &diff;
-                    if self.in_grid(&point) {
-                        anti_nodes.entry(char).or_insert_with(Vec::new).push(point);
-                    }
-                }
+                    (char, a, b)
+                })
+            })
+        })
+    }
+    // the points twice as far from one antenna as from the other, outside the pair
+    pub fn calculate_anti_nodes(&mut self) {
+        let mut anti_nodes = HashMap::new();
+        for (char, a, b) in self.antenna_pairs() {
+            for point in [a.at_ratio(b, -1, 1), a.at_ratio(b, 2, 1)]
+                .into_iter()
+                .flatten()
+                .filter(|point| self.in_grid(point))
+            {
+                anti_nodes.entry(char).or_insert_with(Vec::new).push(point);
             }
         }
 
         self.anti_nodes = anti_nodes
     }
+    // every lattice point in line with the pair
     pub fn calculate_anti_nodes_v2(&mut self) {
+        let (min, max) = (self.map.min().unwrap(), self.map.max().unwrap());
         let mut anti_nodes = HashMap::new();
-        for (&char, antennas) in self.antennas.iter() {
-            for i in 0..antennas.len() {
-                for j in i + 1..antennas.len() {
-                    log::debug!(
-                        "antenna i: {:?}, antenna j: {:?}, diff: {:?}",
-                        antennas[i],
-                        antennas[j],
-                        &antennas[i] - &antennas[j]
-                    );
-                    let diff = &antennas[i] - &antennas[j];
-                    
-                    let entry = anti_nodes.entry(char).or_insert_with(Vec::new);
-                    
-                    entry.push(antennas[j].clone());
-                    let mut k = 1;
-                    loop {
-                        let point = &antennas[j] - &diff.multiply_by_i64(k);
-                        if self.in_grid(&point) {
-                            entry.push(point);
-                        } else {
-                            break;
-                        }
-                        k += 1;
-                    }
-
-                    k = 1;
-                    entry.push(antennas[i].clone());
-                    loop {
-                        let point = &antennas[i] + &diff.multiply_by_i64(k);
-                        if self.in_grid(&point) {
-                            entry.push(point);
-                        } else {
-                            break;
-                        }
-                        k += 1;
-                    }
-                }
-            }
+        for (char, a, b) in self.antenna_pairs() {
+            anti_nodes
+                .entry(char)
+                .or_insert_with(Vec::new)
+                .extend(Line::through(a, b).points_within(min, max));
         }
 
         self.anti_nodes = anti_nodes
@@ -152,52 +109,26 @@ impl Grid {
 }
 
 impl FromStr for Grid {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut y = 0usize;
-        let mut size_x = None;
+        let map = SparseGrid::from_str_with(s, |c, point| match c {
+            'A'..='z' | '0'..='9' => Ok(Some(c)),
+            '.' => Ok(None),
+            other => Err(ParseError::invalid_char(s, point, other)),
+        })?;
         let mut antennas = HashMap::new();
-
-        for line in s.lines() {
-            let mut x = 0usize;
-            for c in line.chars() {
-                match c {
-                    'A'..='z' | '0'..='9' => {
-                        let point = Point {
-                            x: x as i64,
-                            y: y as i64,
-                        };
-                        antennas.entry(c).or_insert_with(Vec::new).push(point);
-                    }
-                    '.' => {}
-                    other => {
-                        return Err(anyhow::anyhow!("invalid character: {}", other));
-                    }
-                }
-                x += 1;
-            }
-
-            if let Some(size_x) = size_x {
-                if x != size_x {
-                    return Err(anyhow::anyhow!(
-                        "grid is not even on x: {} vs {} for row {}",
-                        size_x,
-                        x,
-                        y
-                    ));
-                }
-            } else {
-                size_x = Some(x);
-            }
-            y += 1;
+        for (point, &c) in map.iter() {
+            antennas
+                .entry(c)
+                .or_insert_with(Vec::new)
+                .push(*point);
         }
 
         Ok(Self {
             antennas,
             anti_nodes: Default::default(),
-            size_y: y,
-            size_x: size_x.context("empty grid")?,
+            map,
         })
     }
 }
@@ -1,4 +1,5 @@
-﻿use crate::common::{Context, InputProvider};
+﻿use crate::common::parse_error::ParseError;
+use crate::common::{Context, InputProvider};
 use anyhow::Context as AnyhowContext;
 use std::collections::VecDeque;
 use std::fmt::{Display, Formatter};
@@ -12,8 +13,10 @@ pub fn run(context: &mut Context) {
 
     let checksum = solve(input, false);
     println!("solution 1: {}", checksum);
+    context.set_part_1_answer(checksum);
     let checksum = solve(input, true);
     println!("solution 2: {}", checksum);
+    context.set_part_2_answer(checksum);
 }
 
 fn solve(input: &str, is_v2: bool) -> usize {
@@ -252,19 +255,20 @@ impl File {
 }
 
 impl FromStr for Disk {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut is_free_space = true;
         let mut id = 0;
         Ok(Self::new(
-            s.chars()
-                .into_iter()
-                .map(|c| {
-                    let space: u32 = c.to_digit(10).context("cannot parse space number")?;
+            s.char_indices()
+                .map(|(i, c)| {
+                    let space: u32 = c.to_digit(10).ok_or_else(|| {
+                        ParseError::at(s, i, format!("cannot parse space number '{}'", c))
+                    })?;
                     is_free_space = !is_free_space;
                     if is_free_space {
-                        Ok::<Blocks, anyhow::Error>(Blocks::FreeSpace(space as usize))
+                        Ok(Blocks::FreeSpace(space as usize))
                     } else {
                         let this_id = id;
                         id += 1;
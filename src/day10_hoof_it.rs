@@ -1,7 +1,9 @@
-﻿use crate::common::models::{Grid, Point};
+﻿use crate::common::components::Connectivity;
+use crate::common::models::{Grid, Point};
+use crate::common::parse_error::ParseError;
 use crate::common::{Context, InputProvider};
-use anyhow::Context as AnyhowContext;
-use std::collections::{HashMap, HashSet};
+use rayon::iter::ParallelIterator;
+use std::collections::HashMap;
 use std::str::FromStr;
 
 pub fn run(context: &mut Context) {
@@ -13,12 +15,13 @@ pub fn run(context: &mut Context) {
     let mut map: Map = input.parse().unwrap();
     map.fill_path_scores();
     println!("solution 1: {}", map.trailhead_scores_sum());
+    context.set_part_1_answer(map.trailhead_scores_sum());
     println!("solution 2: {}", map.trailhead_ratings_sum());
+    context.set_part_2_answer(map.trailhead_ratings_sum());
 }
 
 struct Map {
     grid: Grid<u8>,
-    path_scores: HashMap<Point<usize>, HashSet<Point<usize>>>,
     path_ratings: HashMap<Point<usize>, u32>,
 }
 
@@ -26,7 +29,6 @@ impl Map {
     pub fn new(grid: Grid<u8>) -> Self {
         Self {
             grid,
-            path_scores: Default::default(),
             path_ratings: Default::default(),
         }
     }
@@ -38,33 +40,9 @@ impl Map {
     }
     pub fn fill_path_scores(&mut self) {
         for point in self.get_trailheads().collect::<Vec<_>>() {
-            self.fill_next_path(point.clone(), 1);
             self.fill_next_path_ratings(point, 1);
         }
     }
-    fn fill_next_path(&mut self, point: Point<usize>, next_value: u8) -> HashSet<Point<usize>> {
-        if let Some(cached) = self.path_scores.get(&point) {
-            return cached.clone();
-        }
-
-        let mut score = HashSet::new();
-        for next in self
-            .grid
-            .four_way_neighbors(&point)
-            .into_iter()
-            .filter(|p| self.grid.get(p) == Some(&next_value))
-            .collect::<Vec<_>>()
-        {
-            if next_value == 9 {
-                score.insert(next);
-            } else {
-                score.extend(self.fill_next_path(next, next_value + 1));
-            }
-        }
-
-        log::trace!("resolved {} {} = {}", next_value - 1, point, score.len());
-        self.path_scores.entry(point).or_insert(score).clone()
-    }
     fn fill_next_path_ratings(&mut self, point: Point<usize>, next_value: u8) -> u32 {
         if let Some(cached) = self.path_ratings.get(&point) {
             return *cached;
@@ -91,12 +69,18 @@ impl Map {
         score
     }
     pub fn trailhead_scores_sum(&self) -> usize {
-        self.get_trailheads()
-            .map(|t| {
-                self.path_scores
-                    .get(&t)
-                    .expect("trailhead path not calculated")
-                    .len()
+        self.grid
+            .par_iter()
+            .filter(|(_, &value)| value == 0)
+            .map(|(t, _)| {
+                let score = self
+                    .grid
+                    .flood_fill(&t, Connectivity::FourWay, |&from, &to| to == from + 1)
+                    .into_iter()
+                    .filter(|p| self.grid[p] == 9)
+                    .count();
+                log::trace!("trailhead {} has score {}", t, score);
+                score
             })
             .sum()
     }
@@ -113,13 +97,18 @@ impl Map {
 }
 
 impl FromStr for Map {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self::new(Grid::try_from_iter(s.lines().map(|line| {
-            line.chars()
-                .map(|c| c.to_digit(10).context("invalid character").map(|d| d as u8))
-        }))?))
+        Ok(Self::new(Grid::try_from_iter(s.lines().enumerate().map(
+            |(y, line)| {
+                line.chars().enumerate().map(move |(x, c)| {
+                    c.to_digit(10)
+                        .map(|d| d as u8)
+                        .ok_or_else(|| ParseError::invalid_char(s, &Point { x, y }, c))
+                })
+            },
+        ))?))
     }
 }
 
@@ -1,4 +1,5 @@
-﻿use crate::common::{Context, InputProvider};
+﻿use crate::common::parse_error::{parse_str, ParseError};
+use crate::common::{Context, InputProvider};
 use std::collections::{HashMap, LinkedList};
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
@@ -22,6 +23,7 @@ pub fn run(context: &mut Context) {
     }
 
     println!("stones after 25 times: {}", stones.stones_count());
+    context.set_part_1_answer(stones.stones_count());
 
     let mut stones: StonesV2 = input.parse::<Stones>().unwrap().into();
 
@@ -30,6 +32,7 @@ pub fn run(context: &mut Context) {
     }
 
     println!("stones after 75 times: {}", stones.stones_count());
+    context.set_part_2_answer(stones.stones_count());
 }
 
 struct Stones(LinkedList<Stone>);
@@ -53,12 +56,12 @@ struct Stones(LinkedList<Stone>);
 //}
 
 impl FromStr for Stones {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(Self(
             s.split_whitespace()
-                .map(|value| value.parse().map(Stone::new))
+                .map(|value| parse_str(s, value).map(Stone::new))
                 .collect::<Result<_, _>>()?,
         ))
     }
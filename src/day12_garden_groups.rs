@@ -1,7 +1,9 @@
-﻿use crate::common::models::{Direction, Grid, Point};
+﻿use crate::common::components::Connectivity;
+use crate::common::geometry::Region;
+use crate::common::image::{Image, Rgb};
+use crate::common::models::Grid;
+use crate::common::parse_error::ParseError;
 use crate::common::{Context, InputProvider};
-use std::collections::HashSet;
-use std::fmt::{Debug, Formatter};
 use std::str::FromStr;
 
 pub fn run(context: &mut Context) {
@@ -12,347 +14,80 @@ pub fn run(context: &mut Context) {
 
     let price = solve(input);
     println!("solution 1: {}", price);
+    context.set_part_1_answer(price);
     let price = solve2(input);
     println!("solution 2: {}", price);
-    let price = solve2v2(input);
-    println!("solution 2: {}", price);
+    context.set_part_2_answer(price);
+
+    context.save_image("day12_regions.png", || regions_image(input));
 }
 
-fn solve(input: &str) -> usize {
-    let mut total_price = 0usize;
+fn regions_image(input: &str) -> Image {
     let plot: Plot = input.parse().unwrap();
-    let mut used: HashSet<Point<usize>> = HashSet::new();
-    for (point, &value) in plot.0.iter() {
-        if used.contains(&point) {
-            continue;
-        }
-        let mut members = 0;
-        let mut edges = 0;
-        let mut current = Vec::from([point]);
-        while !current.is_empty() {
-            let mut next = Vec::new();
-            for current in current {
-                if used.contains(&current) {
-                    continue;
-                } else {
-                    used.insert(current.clone());
-                }
-                members += 1;
-
-                let neighbors = plot.0.four_way_neighbors(&current);
-                edges += 4 - neighbors.len();
-                for p in neighbors {
-                    let v = plot.0.get(&p).copied().unwrap();
-                    if v == value {
-                        next.push(p);
-                    } else {
-                        edges += 1;
-                    }
-                }
-            }
-
-            current = next;
-        }
-
-        let price = members * edges;
-        log::debug!(
-            "A region of {} plants with price {} * {} = {}.",
-            value,
-            members,
-            edges,
-            price
-        );
-        total_price += price;
-    }
-
-    total_price
+    plot.0
+        .label_regions(Connectivity::FourWay)
+        .labels
+        .to_image(4, |_, &label| Rgb::from_index(label))
 }
-fn solve2(input: &str) -> usize {
-    let plot: Plot = input.parse().unwrap();
-    let mut used: HashSet<Point<usize>> = HashSet::new();
-
-    let mut regions: Vec<Region> = Vec::new();
-
-    for (point, _) in plot.0.iter() {
-        if used.contains(&point) {
-            continue;
-        }
-        let region = Region::build(&plot, point);
-
-        used.extend(region.members.clone());
-        regions.push(region);
-    }
 
-    let mut total_price = 0usize;
-    for mut region in regions.clone() {
-        for other_region in &regions {
-            if !other_region.contained_in.is_empty()
-                && other_region
-                    .contained_in
-                    .iter()
-                    .all(|p| region.members.contains(p))
-            {
-                log::debug!(
-                    "Region {} is inside region {}",
-                    other_region.name,
-                    region.name
-                );
-                region.sides_inside += other_region.sides;
-            }
-        }
-
-        let price = region.members.len() * (region.sides + region.sides_inside);
-        log::debug!(
-            "A region of {} plants with price {} * ({} + {}) = {}.",
-            region.name,
-            region.members.len(),
-            region.sides,
-            region.sides_inside,
+fn solve(input: &str) -> usize {
+    let plot: Plot = input.parse().unwrap();
+    plot.regions()
+        .map(|(name, region)| {
+            let price = region.area() * region.perimeter();
+            log::debug!(
+                "A region of {} plants with price {} * {} = {}.",
+                name,
+                region.area(),
+                region.perimeter(),
+                price
+            );
             price
-        );
-
-        total_price += price;
-    }
-
-    total_price
-}
-
-#[derive(Hash, Ord, PartialOrd, Eq, PartialEq, Clone)]
-struct Edge {
-    position: Point<usize>,
-    direction: Direction,
+        })
+        .sum()
 }
 
-impl Debug for Edge {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}{}",
-            self.position,
-            match self.direction {
-                Direction::Up => "^",
-                Direction::Down => "v",
-                Direction::Left => "<",
-                Direction::Right => ">",
-            }
-        )
-    }
-}
-
-fn solve2v2(input: &str) -> usize {
-    let mut total_price = 0usize;
+fn solve2(input: &str) -> usize {
     let plot: Plot = input.parse().unwrap();
-    let mut used: HashSet<Point<usize>> = HashSet::new();
-    const DIRECTIONS: [Direction; 4] = [
-        Direction::Up,
-        Direction::Down,
-        Direction::Left,
-        Direction::Right,
-    ];
-
-    fn consolidate_edges(edges: &mut HashSet<Edge>, edge: &Edge, plot: &Plot, value: char) {
-        for &direction_func in &[Direction::turn_left, Direction::turn_right] {
-            let mut current = edge.position.clone();
-            let direction = direction_func(&edge.direction);
-            'inner: loop {
-                if let Some(p) = plot.0.try_move_if(&current, direction, |_,&v| v == value) {
-                    if !edges.remove(&Edge {
-                        position: p.clone(),
-                        direction: edge.direction,
-                    }) {
-                        break 'inner;
-                    }
-                    current = p;
-                } else {
-                    break 'inner;
-                }
-            }
-        }
-    }
-
-    for (point, &value) in plot.0.iter() {
-        if used.contains(&point) {
-            continue;
-        }
-
-        let mut members = 0;
-        let mut edges: HashSet<Edge> = Default::default();
-        let mut current_points = vec![point];
-
-        while !current_points.is_empty() {
-            let mut next_points = Vec::new();
-            for current_point in current_points {
-                if used.contains(&current_point) {
-                    continue;
-                }
-                used.insert(current_point.clone());
-                members += 1;
-                for &direction in &DIRECTIONS {
-                    if let Some(p) = plot
-                        .0
-                        .try_move_if(&current_point, direction, |_,&v| v == value)
-                    {
-                        next_points.push(p);
-                    } else {
-                        edges.insert(Edge {
-                            position: current_point.clone(),
-                            direction,
-                        });
-                    }
+    plot.regions()
+        .map(|(name, region)| {
+            let price = region.area() * region.sides();
+            log::debug!(
+                "A region of {} plants with price {} * {} = {}.",
+                name,
+                region.area(),
+                region.sides(),
+                price
+            );
+            if log::log_enabled!(log::Level::Trace) {
+                let holes = region.hole_outlines();
+                if !holes.is_empty() {
+                    log::trace!("{} surrounds {} holes: {:?}", name, holes.len(), holes);
                 }
             }
-            current_points = next_points;
-        }
-
-        for edge in edges.clone() {
-            if edges.contains(&edge) {
-                consolidate_edges(&mut edges, &edge, &plot, value);
-            }
-        }
-
-        let price = members * edges.len();
-        log::trace!("{}: edges: {:?}", value, edges);
-        log::debug!(
-            "A region of {} plants with price {} * {} = {}.",
-            value,
-            members,
-            edges.len(),
             price
-        );
-        total_price += price;
-    }
-
-    total_price
+        })
+        .sum()
 }
 
 #[derive(Clone)]
-struct Region {
-    name: char,
-    members: HashSet<Point<usize>>,
-    sides: usize,
-    sides_inside: usize,
-    contained_in: HashSet<Point<usize>>,
-}
-
-impl Region {
-    pub fn build(plot: &Plot, mut start: Point<usize>) -> Self {
-        let value = *plot.0.get(&start).unwrap();
-
-        'init: loop {
-            let directions = [Direction::Up, Direction::Left];
-            for direction in directions {
-                if let Some(next) = start.move_to(direction) {
-                    if plot.0.get(&next) == Some(&value) {
-                        start = next;
-                        continue 'init;
-                    }
-                }
-            }
-            break;
-        }
-
-        let mut sides = 0;
-        let members = Self::get_members(plot, start.clone());
-        log::trace!("{}: members: {:?}", value, members);
-        log::trace!("{}: start: {}", value, start);
-        let start = start;
-        let start_direction = Direction::Right;
-        let mut current = start.clone();
-        let mut direction = Direction::Right;
-
-        let mut enclosed = true;
-        let mut contained_in = HashSet::new();
-
-        loop {
-            if sides >= 4 && current == start && direction == start_direction {
-                break;
-            }
-            if let Some(next) = current.move_to(direction) {
-                log::trace!("{}: trying to move to {}", value, next);
-                if members.contains(&next) {
-                    log::trace!("{}: {} is a member", value, next);
-                    current = next;
-                    let perpendicular = direction.turn_left();
-                    if let Some(left) = current.move_to(perpendicular) {
-                        if plot.0.get(&left) == Some(&value) {
-                            direction = perpendicular;
-                            sides += 1;
-                            log::trace!(
-                                "{}: ++side = {} moving in perpendicular direction {:?} to {}",
-                                value,
-                                sides,
-                                perpendicular,
-                                current
-                            );
-                        } else {
-                            contained_in.insert(left);
-                        }
-                    } else {
-                        enclosed = false;
-                    }
-
-                    continue;
-                } else {
-                    contained_in.insert(next);
-                }
-            } else {
-                enclosed = false;
-            }
-            direction = direction.turn_right();
-            sides += 1;
-            log::trace!("{}: ++side = {} turning to {:?}", value, sides, direction);
-        }
-
-        if !enclosed {
-            contained_in = Default::default();
-        }
-
-        Self {
-            name: value,
-            members,
-            sides,
-            sides_inside: 0,
-            contained_in,
-        }
-    }
-    fn get_members(plot: &Plot, start: Point<usize>) -> HashSet<Point<usize>> {
-        let value = *plot.0.get(&start).unwrap();
-        let mut members = HashSet::new();
-
-        let mut current = Vec::from([start]);
-        while !current.is_empty() {
-            let mut next = Vec::new();
-            for current in current {
-                if members.contains(&current) {
-                    continue;
-                } else {
-                    members.insert(current.clone());
-                }
-
-                let neighbors = plot.0.four_way_neighbors(&current);
-                for p in neighbors {
-                    let v = plot.0.get(&p).copied().unwrap();
-                    if v == value {
-                        next.push(p);
-                    }
-                }
-            }
-
-            current = next;
-        }
+struct Plot(Grid<char>);
 
-        members
+impl Plot {
+    pub fn regions(&self) -> impl Iterator<Item = (char, Region)> + '_ {
+        self.0
+            .label_regions(Connectivity::FourWay)
+            .stats
+            .into_iter()
+            .map(|component| (self.0[&component.cells[0]], component.region()))
     }
 }
 
-#[derive(Clone)]
-struct Plot(Grid<char>);
-
 impl FromStr for Plot {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self(Grid::from_iter(s.lines().map(|line| line.chars()))))
+        Ok(Self(Grid::from_str_with(s, |c, _| Ok(Some(c)))?))
     }
 }
 
@@ -1,8 +1,8 @@
 ﻿use crate::common::linear_algebra::{self, Matrix, Vector};
 use crate::common::models::Point;
+use crate::common::parse;
+use crate::common::parse_error::{parse_nested, ParseError};
 use crate::common::{Context, InputProvider};
-use once_cell::sync::Lazy;
-use regex::Regex;
 use std::str::FromStr;
 
 pub fn run(context: &mut Context) {
@@ -11,7 +11,11 @@ pub fn run(context: &mut Context) {
     let input = context.get_input();
     let input = input.as_str();
 
-    let games: Vec<Game> = input.split("\n\n").map(|s| s.parse().unwrap()).collect();
+    let games: Vec<Game> = parse::sections(input)
+        .into_iter()
+        .map(|section| parse_nested(input, section))
+        .collect::<Result<_, _>>()
+        .unwrap();
 
     let solution: i64 = games
         .iter()
@@ -19,6 +23,7 @@ pub fn run(context: &mut Context) {
         .map(|solution| solution.x * 3 + solution.y * 1)
         .sum();
     println!("solution 1: {}", solution);
+    context.set_part_1_answer(solution);
 
     let mut games = games;
     for game in games.iter_mut() {
@@ -33,6 +38,7 @@ pub fn run(context: &mut Context) {
         .map(|solution| solution.x * 3 + solution.y * 1)
         .sum();
     println!("solution 2: {}", solution);
+    context.set_part_2_answer(solution);
 }
 
 fn solve_game(game: &Game) -> Option<Point<i64>> {
@@ -68,48 +74,19 @@ struct Button {
 }
 
 impl FromStr for Game {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        static RE: Lazy<Result<Regex, regex::Error>> = Lazy::new(|| {
-            Regex::new(
-                r"Button A: X(?<ax>[-+]\d+), Y(?<ay>[-+]\d+)
-Button B: X(?<bx>[-+]\d+), Y(?<by>[-+]\d+)
-Prize: X=(?<px>[-+]?\d+), Y=(?<py>[-+]?\d+)",
-            )
-        });
-        match &*RE {
-            Err(e) => Err(anyhow::anyhow!(
-                "failed to compile regex: {}",
-                e.to_string().replace("\n", "")
-            )),
-            Ok(re) => {
-                if let Some(caps) = re.captures(s) {
-                    Ok(Game {
-                        button_a: Button {
-                            delta: Point {
-                                x: caps["ax"].parse()?,
-                                y: caps["ay"].parse()?,
-                            },
-                        },
-                        button_b: Button {
-                            delta: Point {
-                                x: caps["bx"].parse()?,
-                                y: caps["by"].parse()?,
-                            },
-                        },
-                        price: Point {
-                            x: caps["px"].parse()?,
-                            y: caps["py"].parse()?,
-                        },
-                    })
-                } else {
-                    Err(anyhow::anyhow!(
-                        "Input string does not match the expected format."
-                    ))
-                }
-            }
-        }
+        let [ax, ay, bx, by, px, py] = parse::integers_n(s)?;
+        Ok(Game {
+            button_a: Button {
+                delta: Point { x: ax, y: ay },
+            },
+            button_b: Button {
+                delta: Point { x: bx, y: by },
+            },
+            price: Point { x: px, y: py },
+        })
     }
 }
 
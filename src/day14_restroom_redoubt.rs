@@ -1,8 +1,8 @@
-﻿use crate::common::models::Point;
+﻿use crate::common::image::{Image, Rgb};
+use crate::common::models::{Grid, Point, Quadrant, Torus};
+use crate::common::parse_error::ParseError;
+use crate::common::parse;
 use crate::common::{Context, InputProvider};
-use anyhow::Context as AnyhowContext;
-use once_cell::sync::Lazy;
-use regex::Regex;
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
@@ -13,11 +13,7 @@ pub fn run(context: &mut Context) {
     let input = context.get_input();
     let input = input.as_str();
 
-    let robots: Vec<Robot> = input
-        .lines()
-        .map(|line| line.parse())
-        .collect::<Result<Vec<_>, _>>()
-        .unwrap();
+    let robots: Vec<Robot> = parse::lines(input).unwrap();
 
     let mut space = if context.is_testing() {
         Space::new_testing()
@@ -30,7 +26,11 @@ pub fn run(context: &mut Context) {
     let mut safety_factors = Vec::new();
 
     log::debug!("initial: {}", space);
+    let mut playback = context.playback();
     for i in 0..100 {
+        if let Some(playback) = &mut playback {
+            playback.push_frame(format!("after {} seconds:{}", i, space));
+        }
         space.tick();
         if i <= 5 {
             log::debug!("after {} seconds: {}", i, space);
@@ -38,7 +38,12 @@ pub fn run(context: &mut Context) {
         safety_factors.push(space.get_safety_factor());
     }
     log::debug!("after 100 seconds: {}", space);
+    if let Some(mut playback) = playback {
+        playback.push_frame(format!("after 100 seconds:{}", space));
+        playback.run().unwrap();
+    }
     println!("safety factor: {}", space.get_safety_factor());
+    context.set_part_1_answer(space.get_safety_factor());
 
     let avg_safety = safety_factors.iter().copied().sum::<usize>() / safety_factors.len();
     println!("avg safety factor: {}", avg_safety);
@@ -59,6 +64,7 @@ pub fn run(context: &mut Context) {
         "min safety factor {} at {} seconds",
         min_safety, min_safety_seconds
     );
+    context.set_part_2_answer(min_safety_seconds);
     safety_factors.sort();
     println!("min safety factors: {:?}", &safety_factors[0..10]);
     
@@ -68,6 +74,7 @@ pub fn run(context: &mut Context) {
         let safety = space.get_safety_factor();
         if safety_factors[0..10].contains(&safety) {
             println!("after {} seconds: {}", i, space);
+            context.save_image(&format!("day14_{:05}.png", i), || space.to_image());
         }
     }
 }
@@ -80,68 +87,64 @@ struct Robot {
 
 #[derive(Clone)]
 struct Space {
-    robots: HashMap<Point<i64>, Vec<Robot>>,
-    size_x: i64,
-    size_y: i64,
+    robots: HashMap<Point<usize>, Vec<Robot>>,
+    torus: Torus,
 }
 
 impl Space {
     pub fn new() -> Self {
         Self {
             robots: Default::default(),
-            size_x: 101,
-            size_y: 103,
+            torus: Torus::new(101, 103),
         }
     }
     pub fn new_testing() -> Self {
         Self {
             robots: Default::default(),
-            size_x: 11,
-            size_y: 7,
+            torus: Torus::new(11, 7),
         }
     }
     pub fn insert_robots(&mut self, robots: impl Iterator<Item = Robot>) {
         for robot in robots {
-            self.insert_robot(robot.start_position.clone(), robot);
+            self.insert_robot(self.torus.wrap(&robot.start_position), robot);
         }
     }
-    fn insert_robot(&mut self, at: Point<i64>, robot: Robot) {
+    fn insert_robot(&mut self, at: Point<usize>, robot: Robot) {
         self.robots.entry(at).or_default().push(robot);
     }
     pub fn tick(&mut self) {
         for (point, robots) in std::mem::take(&mut self.robots) {
             for robot in robots {
-                let mut point = &point + &robot.velocity;
-                point.x = (point.x + self.size_x) % self.size_x;
-                point.y = (point.y + self.size_y) % self.size_y;
+                let point = self.torus.step(&point, &robot.velocity, 1);
                 self.insert_robot(point, robot);
             }
         }
     }
-    pub fn get_safety_factor(&self) -> usize {
-        let mut quadrants = Quadrant::get_quadrants(self);
-
+    pub fn to_image(&self) -> Image {
+        let mut grid: Grid<usize> = Grid::new(self.torus.len_x(), self.torus.len_y());
         for (point, robots) in &self.robots {
-            for quadrant in quadrants.iter_mut() {
-                if quadrant.try_insert(point, robots.len()) {
-                    break;
-                }
-            }
+            grid[point] = robots.len();
         }
+        grid.to_image(4, |_, &count| if count > 0 { Rgb::GREEN } else { Rgb::BLACK })
+    }
+    pub fn get_safety_factor(&self) -> usize {
+        let quadrants = self
+            .torus
+            .quadrant_counts(self.robots.iter().map(|(point, robots)| (point, robots.len())));
 
-        for quadrant in quadrants.iter() {
-            log::debug!("quadrant {:?}: {}", quadrant.quadrant_type, quadrant.count);
+        for (quadrant, count) in Quadrant::quadrants().iter().zip(quadrants) {
+            log::debug!("quadrant {:?}: {}", quadrant, count);
         }
 
-        quadrants.into_iter().map(|q| q.count).product()
+        quadrants.into_iter().product()
     }
 }
 
 impl Display for Space {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for y in 0..self.size_y {
+        for y in 0..self.torus.len_y() {
             write!(f, "\n")?;
-            for x in 0..self.size_x {
+            for x in 0..self.torus.len_x() {
                 let point = Point { x, y };
                 if let Some(robots) = self.robots.get(&point) {
                     write!(f, "{}", robots.len())?;
@@ -154,82 +157,15 @@ impl Display for Space {
     }
 }
 
-#[derive(Debug)]
-enum QuadrantType {
-    NorthWest,
-    NorthEast,
-    SouthWest,
-    SouthEast,
-}
-
-struct Quadrant<'a> {
-    quadrant_type: QuadrantType,
-    space: &'a Space,
-    count: usize,
-}
-
-impl<'a> Quadrant<'a> {
-    fn new(quadrant_type: QuadrantType, space: &'a Space) -> Self {
-        Self {
-            quadrant_type,
-            space,
-            count: 0,
-        }
-    }
-    pub fn get_quadrants(for_space: &'a Space) -> [Self; 4] {
-        [
-            Quadrant::new(QuadrantType::NorthWest, for_space),
-            Quadrant::new(QuadrantType::NorthEast, for_space),
-            Quadrant::new(QuadrantType::SouthWest, for_space),
-            Quadrant::new(QuadrantType::SouthEast, for_space),
-        ]
-    }
-    pub fn try_insert(&mut self, item: &Point<i64>, count: usize) -> bool {
-        if match &self.quadrant_type {
-            QuadrantType::NorthWest => {
-                item.x < self.space.size_x / 2 && item.y < self.space.size_y / 2
-            }
-            QuadrantType::NorthEast => {
-                item.x > self.space.size_x / 2 && item.y < self.space.size_y / 2
-            }
-            QuadrantType::SouthWest => {
-                item.x < self.space.size_x / 2 && item.y > self.space.size_y / 2
-            }
-            QuadrantType::SouthEast => {
-                item.x > self.space.size_x / 2 && item.y > self.space.size_y / 2
-            }
-        } {
-            self.count += count;
-            true
-        } else {
-            false
-        }
-    }
-}
-
 impl FromStr for Robot {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        static RE: Lazy<Result<Regex, regex::Error>> =
-            Lazy::new(|| Regex::new(r"p=(?<px>-?\d+),(?<py>-?\d+) v=(?<vx>-?\d+),(?<vy>-?\d+)"));
-
-        match &*RE {
-            Ok(re) => {
-                let captures = re.captures(s).context("invalid robot input line")?;
-                Ok(Robot {
-                    start_position: Point {
-                        x: captures["px"].parse()?,
-                        y: captures["py"].parse()?,
-                    },
-                    velocity: Point {
-                        x: captures["vx"].parse()?,
-                        y: captures["vy"].parse()?,
-                    },
-                })
-            }
-            Err(e) => Err(anyhow::anyhow!(e.to_string())),
-        }
+        let [px, py, vx, vy] = parse::integers_n(s)?;
+        Ok(Robot {
+            start_position: Point { x: px, y: py },
+            velocity: Point { x: vx, y: vy },
+        })
     }
 }
 
@@ -1,6 +1,8 @@
-﻿use crate::common::models::{Direction, Grid, Point};
+﻿use crate::common::grid_cell::{grid_cell, GridCell};
+use crate::common::models::{Direction, Grid, Point};
+use crate::common::parse;
+use crate::common::parse_error::ParseError;
 use crate::common::{Context, InputProvider};
-use anyhow::Context as AnyhowContext;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
@@ -10,62 +12,89 @@ pub fn run(context: &mut Context) {
     let input = context.get_input();
     let input = input.as_str();
 
-    solve(input, false);
+    let sum_gps = solve(context, input, false);
+    context.set_part_1_answer(sum_gps);
 
-    solve(input, true);
+    let sum_gps = solve(context, input, true);
+    context.set_part_2_answer(sum_gps);
 }
 
-fn solve(input: &str, is_wide: bool) {
-    let mut parts = input.split("\n\n");
-    let mut map: Map = if is_wide {
-        parts.next().unwrap().parse::<WideMap>().unwrap().0
-    } else {
-        parts.next().unwrap().parse().unwrap()
-    };
+fn solve(context: &Context, input: &str, is_wide: bool) -> usize {
+    let [map, moves] = parse::sections_n(input).unwrap();
+    let mut map = parse_map(map, is_wide);
 
     log::debug!("initial state:{}", map);
-    for c in parts.next().unwrap().chars() {
+    let mut playback = context.playback();
+    if let Some(playback) = &mut playback {
+        playback.push_frame(format!("initial state:{}", map));
+    }
+    for (i, c) in moves.char_indices() {
         if c.is_whitespace() {
             continue;
         }
+        let before = log::log_enabled!(log::Level::Trace).then(|| map.grid.clone());
         map.next(
             c.try_into()
-                .ok()
-                .with_context(|| format!("invalid direction: '{}'", c))
+                .map_err(|_| ParseError::at(moves, i, format!("invalid direction: '{}'", c)))
                 .unwrap(),
         );
-        log::trace!("Move {}:{}", c, map);
+        if let Some(before) = before {
+            log::trace!("Move {}:{}", c, before.display_diff(&map.grid));
+        }
+        if let Some(playback) = &mut playback {
+            playback.push_frame(format!("move {}:{}", c, map));
+        }
     }
     log::debug!("end state:{}", map);
+    if let Some(playback) = playback {
+        playback.run().unwrap();
+    }
+
+    let sum_gps = map.sum_gps();
+    println!("sum gps: {}", sum_gps);
+    sum_gps
+}
 
-    println!("sum gps: {}", map.sum_gps());
+fn parse_map(map: &str, is_wide: bool) -> Map {
+    if is_wide {
+        map.parse::<WideMap>().unwrap().0
+    } else {
+        map.parse().unwrap()
+    }
 }
 
 struct Map {
     grid: Grid<Space>,
     robot: Point<usize>,
+    saved: SaveStage,
 }
 
+// one copy of the grid kept for the whole simulation, so undoing a blocked push never allocates
 #[derive(Default)]
 struct SaveStage(Option<Grid<Space>>);
 impl SaveStage {
-    pub fn save(&mut self, source: &Map) {
-        self.0 = Some(source.grid.clone())
+    pub fn save(&mut self, source: &Grid<Space>) {
+        match &mut self.0 {
+            Some(grid) => grid.clone_from(source),
+            None => self.0 = Some(source.clone()),
+        }
     }
-    pub fn load(&mut self, dest: &mut Map) {
-        let data = std::mem::take(&mut self.0);
-        dest.grid = data.unwrap();
+    pub fn load(&self, dest: &mut Grid<Space>) {
+        dest.clone_from(self.0.as_ref().unwrap());
     }
 }
 
 impl Map {
+    // a wide box can move half of its stack before the other half hits a wall,
+    // so a failed push rolls the whole grid back
     pub fn next(&mut self, direction: Direction) {
-        let point = self.robot.clone();
-        self.try_move(&point, direction);
+        let point = self.robot;
+        self.saved.save(&self.grid);
+        if !self.try_move(&point, direction) {
+            self.saved.load(&mut self.grid);
+        }
     }
     fn try_move(&mut self, point: &Point<usize>, direction: Direction) -> bool {
-        let mut data = SaveStage::default();
-
         let point_next = point
             .move_to(direction)
             .expect("weird, the walls disappeared?");
@@ -77,33 +106,19 @@ impl Map {
                 unreachable!()
             }
             Space::WideBox => {
-                data.save(&self);
                 let point_next_2 = point_next
                     .move_to(Direction::Right)
                     .expect("WideBox doesn't have a right side");
 
-                if self.try_move(&point_next_2, direction) && self.try_move(&point_next, direction)
-                {
-                    // Order Important!
-                    true
-                } else {
-                    data.load(self);
-                    false
-                }
+                // Order Important!
+                self.try_move(&point_next_2, direction) && self.try_move(&point_next, direction)
             }
             Space::WideBoxEnd => {
-                data.save(&self);
                 let point_next_2 = point_next
                     .move_to(Direction::Left)
                     .expect("WideBox doesn't have a left side");
-                if self.try_move(&point_next_2, direction) && self.try_move(&point_next, direction)
-                {
-                    // Order Important!
-                    true
-                } else {
-                    data.load(self);
-                    false
-                }
+                // Order Important!
+                self.try_move(&point_next_2, direction) && self.try_move(&point_next, direction)
             }
         } {
             let value_next = *self
@@ -115,7 +130,7 @@ impl Map {
                 .get(&point)
                 .expect("where is the grid coming from?");
             if let Space::Robot = value {
-                self.robot = point_next.clone();
+                self.robot = point_next;
             }
             log::trace!(
                 "swapping {} {} and {} {}",
@@ -142,7 +157,7 @@ impl Map {
     }
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, PartialEq, Eq)]
 enum Space {
     Empty,
     Wall,
@@ -158,43 +173,40 @@ impl Default for Space {
     }
 }
 
+grid_cell!(Space {
+    Empty => '.',
+    Wall => '#',
+    Box => 'O',
+    Robot => '@',
+    WideBox => '[',
+    WideBoxEnd => ']',
+});
+
 impl FromStr for Map {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let size_y = s.lines().count();
-        let first_line = s.lines().next().context("empty map")?;
-        let size_x = first_line.chars().count();
-        if first_line.chars().any(|c| c != '#') {
-            return Err(anyhow::anyhow!("first line should be all walls"));
+        let first_line = s
+            .lines()
+            .next()
+            .ok_or_else(|| ParseError::missing(s, "a map"))?;
+        if let Some((i, _)) = first_line.char_indices().find(|&(_, c)| c != '#') {
+            return Err(ParseError::at(s, i, "first line should be all walls"));
         }
 
-        let mut grid: Grid<Space> = Grid::new(size_x, size_y);
-        let mut robot: Option<Point<usize>> = None;
-
-        for (y, line) in s.lines().enumerate() {
-            for (x, c) in line.chars().enumerate() {
-                let point = Point { x, y };
-                match c {
-                    '.' => {}
-                    '#' => {
-                        grid.set(&point, Space::Wall);
-                    }
-                    'O' => {
-                        grid.set(&point, Space::Box);
-                    }
-                    '@' => {
-                        grid.set(&point, Space::Robot);
-                        robot = Some(point);
-                    }
-                    _ => return Err(anyhow::anyhow!("invalid character: {}", c)),
-                }
-            }
-        }
+        let grid = Grid::from_str_with(s, |c, point| match Space::parse_char(s, point, c)? {
+            Space::WideBox | Space::WideBoxEnd => Err(ParseError::invalid_char(s, point, c)),
+            space => Ok(Some(space)),
+        })?;
+        let robot = grid
+            .iter()
+            .find(|(_, space)| matches!(space, Space::Robot))
+            .map(|(point, _)| point);
 
         Ok(Self {
             grid,
-            robot: robot.context("robot not found")?,
+            robot: robot.ok_or_else(|| ParseError::missing(s, "a robot"))?,
+            saved: SaveStage::default(),
         })
     }
 }
@@ -202,14 +214,17 @@ impl FromStr for Map {
 struct WideMap(Map);
 
 impl FromStr for WideMap {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let size_y = s.lines().count();
-        let first_line = s.lines().next().context("empty map")?;
+        let first_line = s
+            .lines()
+            .next()
+            .ok_or_else(|| ParseError::missing(s, "a map"))?;
         let size_x = first_line.chars().count() * 2;
-        if first_line.chars().any(|c| c != '#') {
-            return Err(anyhow::anyhow!("first line should be all walls"));
+        if let Some((i, _)) = first_line.char_indices().find(|&(_, c)| c != '#') {
+            return Err(ParseError::at(s, i, "first line should be all walls"));
         }
 
         let mut grid: Grid<Space> = Grid::new(size_x, size_y);
@@ -219,59 +234,38 @@ impl FromStr for WideMap {
             for (x, c) in line.chars().enumerate() {
                 let point = Point { x: 2 * x, y };
                 let point2 = Point { x: 2 * x + 1, y };
-                match c {
-                    '.' => {}
-                    '#' => {
+                match Space::parse_char(s, &Point { x, y }, c)? {
+                    Space::Empty => {}
+                    Space::Wall => {
                         grid.set(&point, Space::Wall);
                         grid.set(&point2, Space::Wall);
                     }
-                    'O' => {
+                    Space::Box => {
                         grid.set(&point, Space::WideBox);
                         grid.set(&point2, Space::WideBoxEnd);
                     }
-                    '@' => {
+                    Space::Robot => {
                         grid.set(&point, Space::Robot);
                         robot = Some(point);
                     }
-                    _ => return Err(anyhow::anyhow!("invalid character: {}", c)),
+                    Space::WideBox | Space::WideBoxEnd => {
+                        return Err(ParseError::invalid_char(s, &Point { x, y }, c))
+                    }
                 }
             }
         }
 
         Ok(Self(Map {
             grid,
-            robot: robot.context("robot not found")?,
+            robot: robot.ok_or_else(|| ParseError::missing(s, "a robot"))?,
+            saved: SaveStage::default(),
         }))
     }
 }
 
-impl Display for Space {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Space::Empty => write!(f, "."),
-            Space::Wall => write!(f, "#"),
-            Space::Box => write!(f, "O"),
-            Space::Robot => write!(f, "@"),
-            Space::WideBox => write!(f, "["),
-            Space::WideBoxEnd => write!(f, "]"),
-        }
-    }
-}
-
 impl Display for Map {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for y in 0..self.grid.len_y() {
-            write!(f, "\n")?;
-            for x in 0..self.grid.len_x() {
-                let point = Point { x, y };
-                if let Some(space) = self.grid.get(&point) {
-                    write!(f, "{}", space)?;
-                } else {
-                    write!(f, ".")?;
-                }
-            }
-        }
-        Ok(())
+        write!(f, "{}", self.grid)
     }
 }
 
@@ -312,3 +306,36 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^",
     .into_iter()
     .map(|input| Box::new(move || input.into()) as Box<InputProvider>)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::snapshot::assert_snapshot;
+
+    #[test]
+    fn test_narrow_map_rejects_wide_boxes() {
+        assert!("#####\n#@O.#\n#####".parse::<Map>().is_ok());
+        let err = "#####\n#@[]#\n#####".parse::<Map>().err().unwrap();
+        assert_eq!((err.line(), err.column()), (2, 3));
+        assert!(err.to_string().contains('['));
+    }
+    #[test]
+    fn test_final_warehouse_snapshots() {
+        let input = get_test_inputs().next().unwrap()();
+        let [map, moves] = parse::sections_n(input.as_str()).unwrap();
+        for (name, is_wide) in [("day15_warehouse", false), ("day15_wide_warehouse", true)] {
+            let mut warehouse = parse_map(map, is_wide);
+            for c in moves.chars().filter(|c| !c.is_whitespace()) {
+                warehouse.next(c.try_into().unwrap());
+            }
+            assert_snapshot(
+                name,
+                &warehouse
+                    .grid
+                    .to_snapshot()
+                    .with("robot", warehouse.robot)
+                    .with("sum gps", warehouse.sum_gps()),
+            );
+        }
+    }
+}
@@ -1,11 +1,14 @@
-﻿use crate::common::models::{Direction, Grid, Point};
+﻿use crate::common::grid_cell::{grid_cell, GridCell};
+use crate::common::image::Rgb;
+use crate::common::junctions::Corridor;
+use crate::common::models::{Direction, Grid, Point};
+use crate::common::parse_error::ParseError;
+use crate::common::search;
+use crate::common::styled::Style;
 use crate::common::{Context, InputProvider};
-use anyhow::Context as AnyhowContext;
 use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Display, Formatter};
-use std::hash::{Hash, Hasher};
 use std::str::FromStr;
-use utils::a_star::{self, CurrentNodeDetails, CustomNode, Node, Options, Successor};
 
 pub fn run(context: &mut Context) {
     context.add_test_inputs(get_test_inputs());
@@ -13,138 +16,149 @@ pub fn run(context: &mut Context) {
     let input = context.get_input();
     let input = input.as_str();
 
-    solve(input);
+    let (shortest_path_cost, tiles_in_paths) = solve(context, input);
+    context.set_part_1_answer(shortest_path_cost);
+    context.set_part_2_answer(tiles_in_paths);
 }
 
-fn solve(input: &str) {
-    let game: Game = input.parse().unwrap();
+fn solve(context: &Context, input: &str) -> (usize, usize) {
+    let mut game: Game = input.parse().unwrap();
+    let (shortest_path_cost, tiles_in_path) = find_best_paths(&mut game);
 
+    context.save_image("day16_best_paths.png", || {
+        game.field.0.to_image_with_overrides(
+            4,
+            |_, space| match space {
+                Space::Empty => Rgb::BLACK,
+                Space::Wall => Rgb::GRAY,
+            },
+            tiles_in_path.iter().map(|point| (point, &Rgb::YELLOW)).collect(),
+        )
+    });
+
+    let game = GameWithAllResults::new(&game, &tiles_in_path);
+    println!("all tiles in all shortest paths:{}", game);
+
+    println!("all tiles in all shortest paths:{}", tiles_in_path.len());
+
+    (shortest_path_cost, tiles_in_path.len())
+}
+
+// marks one shortest path on the game, returns its cost and the tiles of every shortest path
+fn find_best_paths(game: &mut Game) -> (usize, HashSet<Point<usize>>) {
     let start = Position {
-        position: game.start.clone(),
+        position: game.start,
         direction: Direction::Right,
     };
-    let end = Position {
-        position: game.end.clone(),
-        direction: Direction::Right,
+    let end = game.end;
+    let graph = game
+        .field
+        .0
+        .junction_graph(|_, space| matches!(space, Space::Empty), [game.start, end]);
+    log::debug!(
+        "maze contracted to {} junctions and {} corridors",
+        graph.len(),
+        graph.corridor_count()
+    );
+    let start_arrival = Arrival {
+        junction: graph.index_of(&game.start).unwrap(),
+        direction: start.direction,
+        corridor: None,
     };
-    let field = &game.field;
-
-    let options = Options::default();
-
-    let get_successors = |position: &Position| {
-        let mut successors = vec![
-            Successor::new(position.turned_clockwise(), 1_000),
-            Successor::new(position.turned_anticlockwise(), 1_000),
-        ];
-        if let Some(pos) =
-            field
-                .0
-                .try_move_if(&position.position, position.direction, |_, space| {
-                    matches!(space, Space::Empty)
-                })
-        {
-            successors.push(Successor::new(
-                Position {
-                    position: pos,
-                    direction: position.direction,
-                },
-                1,
-            ));
-        }
-
-        successors
+    let end_junction = graph.index_of(&end).unwrap();
+
+    let get_successors = |arrival: &Arrival| {
+        graph
+            .corridors_from(arrival.junction)
+            .map(|(id, corridor)| {
+                let turns = arrival
+                    .direction
+                    .quarter_turns_to(&corridor.start_direction)
+                    + corridor.turns;
+                (
+                    Arrival {
+                        junction: corridor.to,
+                        direction: corridor.end_direction,
+                        corridor: Some(id),
+                    },
+                    turns * 1_000 + corridor.length,
+                )
+            })
+            .collect::<Vec<_>>()
     };
 
-    fn distance_function(node_details: CurrentNodeDetails<Position, usize>, end: &Position) -> usize {
-        node_details
-            .current_node
-            .position
-            .manhattan_distance(&end.position)
-    }
-
-    let result = a_star::a_star_search(
-        start.clone(),
+    let result = search::a_star(
+        start_arrival.clone(),
         get_successors,
-        |current| distance_function(current, &end),
-        |left| left.position == end.position,
-        Some(&options),
+        |current| graph.junction(current.junction).manhattan_distance(&end),
+        |current| current.junction == end_junction,
     )
     .unwrap();
 
-    let mut game = game;
-    println!("shortest path len:{}", result.shortest_path.len());
-    game.set_shortest_path(result.shortest_path.into_iter());
+    let all_results = search::all_shortest_paths(start_arrival, get_successors, |current| {
+        current.junction == end_junction
+    })
+    .unwrap();
+    log::debug!(
+        "{} end states reached at cost {}",
+        all_results.goals.len(),
+        all_results.cost
+    );
+
+    let shortest_path = walk_corridors(
+        start,
+        result
+            .nodes
+            .iter()
+            .filter_map(|arrival| arrival.corridor)
+            .map(|id| graph.corridor(id)),
+    );
+    println!("shortest path len:{}", shortest_path.len());
+    game.set_shortest_path(shortest_path.into_iter());
     println!("shortest path:{}", game);
 
-    let shortest_path_cost = result.shortest_path_cost;
+    let shortest_path_cost = result.cost;
     println!("shortest path cost: {}", shortest_path_cost);
 
-    let game: Game = input.parse().unwrap();
-
-    let start = PositionWithHistory {
-        position: Position {
-            position: game.start.clone(),
-            direction: Direction::Right,
-        },
-        history: 0,
-    };
-    let end = PositionWithHistory {
-        position: Position {
-            position: game.end.clone(),
-            direction: Direction::Right,
-        },
-        history: 0,
-    };
-    let field = &game.field;
-
-    let options = Options::default();
-
-    let get_successors = |position: &PositionWithHistory| {
-        let mut successors = vec![
-            Successor::new(position.turned_clockwise(), 1_000),
-            Successor::new(position.turned_anticlockwise(), 1_000),
-        ];
-        if let Some(pos) = field.0.try_move_if(
-            &position.position.position,
-            position.position.direction,
-            |_, space| matches!(space, Space::Empty),
-        ) {
-            successors.push(Successor::new(position.with_new_position(pos), 1));
-        }
-
-        successors
-    };
-
-    fn distance_function_2(node_details: CurrentNodeDetails<PositionWithHistory, usize>, end: &PositionWithHistory) -> usize {
-        node_details
-            .current_node
-            .position
-            .position
-            .manhattan_distance(&end.position.position)
-    }
-
-    let all_results = a_star::a_star_search_all_with_max_score(
-        shortest_path_cost,
-        start,
-        get_successors,
-        |current| distance_function_2(current, &end),
-        |left| left.position.position == end.position.position,
-        Some(&options),
-    )
-    .unwrap();
-    println!("all shortest paths len: {}", all_results.len());
-
     let tiles_in_path: HashSet<Point<usize>> = all_results
-        .iter()
-        .flat_map(|result| result.shortest_path.iter())
-        .map(|position| &position.position.position)
-        .cloned()
+        .nodes_on_paths()
+        .into_iter()
+        .filter_map(|arrival| arrival.corridor)
+        .flat_map(|id| graph.corridor(id).cells().iter().copied())
         .collect();
 
-    let game = GameWithAllResults::new(&game, &tiles_in_path);
-    println!("all tiles in all shortest paths:{}", game);
+    (shortest_path_cost, tiles_in_path)
+}
 
-    println!("all tiles in all shortest paths:{}", tiles_in_path.len());
+// replays the corridors cell by cell, turning in place like the reindeer would
+fn walk_corridors<'a>(
+    start: Position,
+    corridors: impl Iterator<Item = &'a Corridor>,
+) -> Vec<Position> {
+    let mut positions = vec![start];
+    for (direction, next) in corridors.flat_map(|corridor| corridor.steps()) {
+        let mut current = positions.last().unwrap().clone();
+        while current.direction != direction {
+            current = if current.direction.turn_left() == direction {
+                current.turned_anticlockwise()
+            } else {
+                current.turned_clockwise()
+            };
+            positions.push(current.clone());
+        }
+        positions.push(Position {
+            position: next,
+            direction,
+        });
+    }
+    positions
+}
+
+#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+struct Arrival {
+    junction: usize,
+    direction: Direction,
+    corridor: Option<usize>,
 }
 
 #[derive(Hash, Ord, PartialOrd, Eq, PartialEq, Debug, Clone)]
@@ -153,70 +167,21 @@ struct Position {
     direction: Direction,
 }
 
-#[derive(Clone, Debug)]
-struct PositionWithHistory {
-    position: Position,
-    history: u64,
-}
-
 impl Position {
     pub fn turned_clockwise(&self) -> Self {
         Self {
-            position: self.position.clone(),
+            position: self.position,
             direction: self.direction.turn_right(),
         }
     }
     pub fn turned_anticlockwise(&self) -> Self {
         Self {
-            position: self.position.clone(),
+            position: self.position,
             direction: self.direction.turn_left(),
         }
     }
 }
 
-impl PositionWithHistory {
-    pub fn turned_clockwise(&self) -> Self {
-        Self {
-            position: self.position.turned_clockwise(),
-            history: self.get_node_id(),
-        }
-    }
-    pub fn turned_anticlockwise(&self) -> Self {
-        Self {
-            position: self.position.turned_anticlockwise(),
-            history: self.get_node_id(),
-        }
-    }
-    pub fn with_new_position(&self, position: Point<usize>) -> Self {
-        Self {
-            position: Position {
-                position,
-                direction: self.position.direction,
-            },
-            history: self.get_node_id(),
-        }
-    }
-}
-
-impl Node for Position {}
-
-impl CustomNode for PositionWithHistory {
-    const NODE_ID_AND_POSITION_HASH_SAME: bool = false;
-
-    fn get_node_id(&self) -> u64 {
-        let mut hasher = std::collections::hash_map::DefaultHasher::new();
-        self.position.hash(&mut hasher);
-        self.history.hash(&mut hasher);
-        hasher.finish()
-    }
-
-    fn get_position_hash(&self) -> u64 {
-        let mut hasher = std::collections::hash_map::DefaultHasher::new();
-        self.position.hash(&mut hasher);
-        hasher.finish()
-    }
-}
-
 #[derive(Copy, Clone, Debug)]
 enum Space {
     Empty,
@@ -229,6 +194,11 @@ impl Default for Space {
     }
 }
 
+grid_cell!(Space {
+    Empty => '.',
+    Wall => '#',
+});
+
 struct Field(Grid<Space>);
 
 struct Game {
@@ -248,36 +218,27 @@ impl Game {
 }
 
 impl FromStr for Game {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let size_y = s.lines().count();
-        let size_x = s.lines().next().context("empty game map")?.chars().count();
-        let mut grid = Grid::new(size_x, size_y);
-        let mut start: Option<Point<usize>> = None;
-        let mut end: Option<Point<usize>> = None;
-
-        for (y, line) in s.lines().enumerate() {
-            for (x, c) in line.chars().enumerate() {
-                let point = Point { x, y };
-                match c {
-                    '#' => grid.set(&point, Space::Wall),
-                    '.' => {}
-                    'S' => {
-                        start = Some(point);
-                    }
-                    'E' => {
-                        end = Some(point);
-                    }
-                    other => return Err(anyhow::anyhow!("invalid character '{}'", other)),
-                }
+        let mut start = None;
+        let mut end = None;
+        let grid = Grid::from_str_with(s, |c, point| match c {
+            'S' => {
+                start = Some(*point);
+                Ok(None)
             }
-        }
+            'E' => {
+                end = Some(*point);
+                Ok(None)
+            }
+            other => Space::parse_char(s, point, other).map(Some),
+        })?;
 
         Ok(Self {
             field: Field(grid),
-            start: start.context("no start position found")?,
-            end: end.context("no end position found")?,
+            start: start.ok_or_else(|| ParseError::missing(s, "a start position"))?,
+            end: end.ok_or_else(|| ParseError::missing(s, "an end position"))?,
             shortest_path: Default::default(),
         })
     }
@@ -296,14 +257,7 @@ impl Display for Game {
                 } else if let Some(direction) = self.shortest_path.get(&point) {
                     write!(f, "{}", direction)?;
                 } else {
-                    match self.field.0.get(&point).unwrap() {
-                        Space::Empty => {
-                            write!(f, ".")?;
-                        }
-                        Space::Wall => {
-                            write!(f, "#")?;
-                        }
-                    }
+                    write!(f, "{}", self.field.0[&point])?;
                 }
             }
         }
@@ -322,26 +276,17 @@ impl<'a> GameWithAllResults<'a> {
 
 impl Display for GameWithAllResults<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for y in 0..self.0.field.0.len_y() {
-            writeln!(f)?;
-            for x in 0..self.0.field.0.len_x() {
-                let point = Point { x, y };
-                if self.1.contains(&point) {
-                    write!(f, "O")?;
-                } else {
-                    match self.0.field.0.get(&point).unwrap() {
-                        Space::Empty => {
-                            write!(f, ".")?;
-                        }
-                        Space::Wall => {
-                            write!(f, "#")?;
-                        }
-                    }
-                }
-            }
-        }
-
-        Ok(())
+        let overrides = self.1.iter().map(|point| (point, &'O')).collect();
+        let styled = self
+            .0
+            .field
+            .0
+            .styled_with_overrides(overrides, |_, space, tile| match (space, tile) {
+                (_, Some(_)) => Style::new().foreground(Rgb::YELLOW).bold(),
+                (Space::Wall, None) => Style::new().foreground(Rgb::GRAY),
+                (Space::Empty, None) => Style::new(),
+            });
+        write!(f, "{}", styled)
     }
 }
 
@@ -383,3 +328,27 @@ fn get_test_inputs() -> impl Iterator<Item = Box<InputProvider>> {
     .into_iter()
     .map(|input| Box::new(move || input.into()) as Box<InputProvider>)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::snapshot::{assert_snapshot, Snapshot};
+
+    #[test]
+    fn test_best_path_snapshots() {
+        for (i, input) in get_test_inputs().enumerate() {
+            let mut game: Game = input().as_str().parse().unwrap();
+            let (cost, tiles) = find_best_paths(&mut game);
+            assert_snapshot(
+                &format!("day16_best_path_{}", i + 1),
+                &Snapshot::new(&game).with("cost", cost),
+            );
+            let overrides = tiles.iter().map(|point| (point, &'O')).collect();
+            assert_snapshot(
+                &format!("day16_all_best_paths_{}", i + 1),
+                &Snapshot::new(game.field.0.display_with_overrides(overrides))
+                    .with("tiles", tiles.len()),
+            );
+        }
+    }
+}
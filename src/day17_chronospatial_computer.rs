@@ -1,6 +1,7 @@
 use std::collections::VecDeque;
+use crate::common::parse;
+use crate::common::parse_error::{parse_nested, parse_str, ParseError};
 use crate::common::{Context, InputProvider};
-use anyhow::Context as AnyhowContext;
 use std::str::FromStr;
 
 pub fn run(context: &mut Context) {
@@ -9,32 +10,33 @@ pub fn run(context: &mut Context) {
     let input = context.get_input();
     let input = input.as_str();
 
-    run1(input, None);
+    let output = run1(input, None);
+    context.set_part_1_answer(output);
 
-    solve(input);
+    let a = solve(input);
+    context.set_part_2_answer(a);
 
     run1(input, Some(190384609508367));
 }
 
-fn run1(input: &str, override_a: Option<u64>) {
-    let mut parts = input.split("\n\n");
-
-    let mut program = Program {
-        registers: parts.next().unwrap().parse().unwrap(),
+fn parse_program(input: &str) -> Result<(Program, Vec<Instruction>), ParseError> {
+    let [registers, instructions] = parse::sections_n(input)?;
+    let program = Program {
+        registers: parse_nested(input, registers)?,
         output: Default::default(),
         program_counter: 0,
     };
+    let (_, instructions) = parse::key_value(instructions, ":")
+        .map_err(|e| e.relocate(input, instructions))?;
+    let instructions = instructions
+        .split(',')
+        .map(|n| parse_nested(input, n))
+        .collect::<Result<_, _>>()?;
+    Ok((program, instructions))
+}
 
-    let instructions: Vec<Instruction> = parts
-        .next()
-        .unwrap()
-        .split_whitespace()
-        .nth(1)
-        .unwrap()
-        .split(",")
-        .map(|n| n.parse())
-        .collect::<Result<_, _>>()
-        .unwrap();
+fn run1(input: &str, override_a: Option<u64>) -> String {
+    let (mut program, instructions) = parse_program(input).unwrap();
 
     if let Some(override_a) = override_a {
         program.registers.a = override_a;
@@ -45,24 +47,11 @@ fn run1(input: &str, override_a: Option<u64>) {
     }
 
     println!("output: {}", program.get_output());
+    program.get_output()
 }
 
-fn solve(input: &str) {
-    let mut parts = input.split("\n\n");
-
-    let program = Program {
-        registers: parts.next().unwrap().parse().unwrap(),
-        output: Default::default(),
-        program_counter: 0,
-    };
-
-    let instructions_str = parts.next().unwrap().split_whitespace().nth(1).unwrap();
-
-    let instructions: Vec<Instruction> = instructions_str
-        .split(",")
-        .map(|n| n.parse())
-        .collect::<Result<_, _>>()
-        .unwrap();
+fn solve(input: &str) -> u64 {
+    let (program, instructions) = parse_program(input).unwrap();
 
     let solve_for_a = |out_values: String, a_aggregate: u64| {
         for a in 0..8u64.pow(5) {
@@ -98,6 +87,7 @@ fn solve(input: &str) {
     }
 
     println!("lowest A that solves: {}", a);
+    a
 }
 
 #[derive(Debug, Clone)]
@@ -258,31 +248,21 @@ impl Operations {
 }
 
 impl FromStr for Instruction {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self(s.parse()?))
+        Ok(Self(parse_str(s, s)?))
     }
 }
 
 impl FromStr for Registers {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.lines();
-        let mut get_next = || {
-            parts
-                .next()
-                .context("not enough register lines")
-                .and_then(|line| line.split_whitespace().last().context("empty line"))
-                .and_then(|word| word.parse::<u64>().context("register parse fail"))
-        };
-
-        Ok(Self {
-            a: get_next()?,
-            b: get_next()?,
-            c: get_next()?,
-        })
+        match parse::key_values(s, ":")?.as_slice() {
+            &[("Register A", a), ("Register B", b), ("Register C", c)] => Ok(Self { a, b, c }),
+            _ => Err(ParseError::at(s, 0, "expected registers A, B and C")),
+        }
     }
 }
 
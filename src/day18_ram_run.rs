@@ -1,8 +1,11 @@
+use crate::common::bit_grid::BitGrid;
+use crate::common::dsu::GridDsu;
+use crate::common::grid_cell::grid_cell;
+use crate::common::image::Rgb;
 use crate::common::models::{Grid, Point};
+use crate::common::parse;
+use crate::common::styled::Style;
 use crate::common::{Context, InputProvider};
-use std::fmt::{Display, Formatter};
-use utils::a_star::{a_star_search, ComputationResult, Options, Successor};
-use utils::bisection_method;
 
 pub fn run(context: &mut Context) {
     context.add_test_inputs(get_test_inputs());
@@ -28,89 +31,105 @@ pub fn run(context: &mut Context) {
         memory_space.grid
     );
 
-    let (shortest_path, shortest_path_cost) = solve(&memory_space, None).unwrap();
-    log::debug!(
-        "shortest path:{}",
-        memory_space
-            .grid
-            .display_with_overrides(shortest_path.iter().map(|p| (p, &'O')).collect())
-    );
+    let shortest_path_cost = steps_to_exit(&memory_space).expect("no path to the exit");
+    if log::log_enabled!(log::Level::Debug) {
+        let shortest_path = solve(&memory_space).expect("no path to the exit");
+        log::debug!(
+            "shortest path:{}",
+            memory_space
+                .grid
+                .styled_with_overrides(
+                    shortest_path.iter().map(|p| (p, &'O')).collect(),
+                    |_, space, step| match (space, step) {
+                        (_, Some(_)) => Style::new().foreground(Rgb::GREEN).bold(),
+                        (Space::Corrupted, None) => Style::new().foreground(Rgb::RED),
+                        (Space::Empty, None) => Style::new(),
+                    }
+                )
+        );
+    }
     println!("shortest path cost: {}", shortest_path_cost);
+    context.set_part_1_answer(shortest_path_cost);
 
-    let memory_space = MemorySpace::new(Grid::new(grid_size, grid_size));
-
-    let options = Options::default().with_no_logs();
-    let first_preventing_exit = bisection_method::find_first_true(
-        |corruption_size| {
-            let mut memory_space = memory_space.clone();
-            for corruption in corruption.iter().take(corruption_size + 1) {
-                memory_space.add_corruption(corruption);
-            }
-            if let Ok((path, _cost)) = solve(&memory_space, Some(&options)) {
-                log::debug!(
-                    "corruption index {} still has an exit:{}",
-                    corruption_size,
-                    memory_space
-                        .grid
-                        .display_with_overrides(path.iter().map(|p| (p, &'O')).collect())
-                );
-                false
-            } else {
-                log::debug!(
-                    "corruption index {} has NO exit:{}",
-                    corruption_size,
-                    memory_space.grid
-                );
-                true
-            }
-        },
-        0,
-        corruption.len() - 1,
-    )
-    .expect("first blocking exit not found");
+    let first_preventing_exit =
+        first_blocking_byte(grid_size, &corruption).expect("first blocking exit not found");
 
     println!(
         "first blocking exit is {}: {}",
         first_preventing_exit, corruption[first_preventing_exit]
     );
+    context.set_part_2_answer(corruption[first_preventing_exit]);
 }
 
-fn solve(
-    memory_space: &MemorySpace,
-    options: Option<&Options>,
-) -> Result<(Vec<Point<usize>>, usize), anyhow::Error> {
+fn solve(memory_space: &MemorySpace) -> Option<Vec<Point<usize>>> {
     let start = Point::new(0, 0);
     let end = Point::new(memory_space.grid.len_x() - 1, memory_space.grid.len_y() - 1);
-    let ComputationResult {
-        shortest_path,
-        shortest_path_cost,
-    } = a_star_search(
-        start,
-        |node| {
-            memory_space
-                .grid
-                .four_way_neighbors(node)
-                .into_iter()
-                .filter(|next| matches!(memory_space.grid.get(&next), Some(Space::Empty)))
-                .map(|next| Successor::new(next, 1))
-                .collect()
-        },
-        |details| details.current_node.manhattan_distance(&end),
-        |left| left == &end,
-        options,
-    )?;
-    Ok((shortest_path, shortest_path_cost))
+    memory_space
+        .grid
+        .distances_from(&start, |_, space| matches!(space, Space::Empty))
+        .path_to(&end)
+}
+
+// expands the whole BFS frontier at once instead of visiting cells one by one
+fn steps_to_exit(memory_space: &MemorySpace) -> Option<usize> {
+    let grid = &memory_space.grid;
+    let end = Point::new(grid.len_x() - 1, grid.len_y() - 1);
+    let empty = grid.to_bit_grid(|_, space| matches!(space, Space::Empty));
+    let mut frontier = BitGrid::from_points(grid.len_x(), grid.len_y(), [&Point::new(0, 0)]);
+    let mut visited = frontier.clone();
+    for steps in 0.. {
+        if frontier.contains(&end) {
+            return Some(steps);
+        }
+        if frontier.is_empty() {
+            break;
+        }
+        frontier = frontier.neighbors();
+        frontier.intersect_with(&empty);
+        frontier.difference_with(&visited);
+        visited.union_with(&frontier);
+        log::trace!("frontier after {} steps:{}", steps + 1, frontier);
+    }
+    None
+}
+
+fn first_blocking_byte(grid_size: usize, corruption: &[Point<usize>]) -> Option<usize> {
+    let mut memory_space = MemorySpace::new(Grid::new(grid_size, grid_size));
+    for corruption in corruption {
+        memory_space.add_corruption(corruption);
+    }
+    let start = Point::new(0, 0);
+    let end = Point::new(grid_size - 1, grid_size - 1);
+
+    let mut regions = GridDsu::for_grid(&memory_space.grid);
+    for (point, _) in memory_space.grid.iter() {
+        memory_space.connect_neighbors(&mut regions, &point);
+    }
+    if regions.connected(&start, &end) {
+        return None;
+    }
+
+    for (i, byte) in corruption.iter().enumerate().rev() {
+        memory_space.grid.set(byte, Space::Empty);
+        memory_space.connect_neighbors(&mut regions, byte);
+        if regions.connected(&start, &end) {
+            log::debug!(
+                "removing corruption index {} reconnects the exit:{}",
+                i,
+                memory_space.grid
+            );
+            return Some(i);
+        }
+    }
+    None
 }
 
 fn parse(input: &str) -> Vec<Point<usize>> {
     input
         .lines()
         .map(|line| {
-            let mut parts = line.split(',');
-            Point {
-                x: parts.next().unwrap().parse().unwrap(),
-                y: parts.next().unwrap().parse().unwrap(),
-            }
+            let [x, y] = parse::integers_n(line).unwrap();
+            Point { x, y }
         })
         .collect()
 }
@@ -132,6 +151,16 @@ impl MemorySpace {
         self.fallen_bytes += 1;
         self.grid.set(corruption, Space::Corrupted);
     }
+    fn connect_neighbors(&self, regions: &mut GridDsu, point: &Point<usize>) {
+        if !matches!(self.grid.get(point), Some(Space::Empty)) {
+            return;
+        }
+        for neighbor in self.grid.four_way_neighbors(point) {
+            if matches!(self.grid.get(&neighbor), Some(Space::Empty)) {
+                regions.union(point, &neighbor);
+            }
+        }
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -146,19 +175,10 @@ impl Default for Space {
     }
 }
 
-impl Display for Space {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Space::Empty => {
-                write!(f, ".")?;
-            }
-            Space::Corrupted => {
-                write!(f, "#")?;
-            }
-        }
-        Ok(())
-    }
-}
+grid_cell!(Space {
+    Empty => '.',
+    Corrupted => '#',
+});
 
 fn get_test_inputs() -> impl Iterator<Item = Box<InputProvider>> {
     ["5,4
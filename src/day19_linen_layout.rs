@@ -1,3 +1,5 @@
+use crate::common::parse;
+use crate::common::parse_error::{parse_nested, ParseError};
 use crate::common::{Context, InputProvider};
 use std::cell::RefCell;
 use std::collections::HashMap;
@@ -11,15 +13,9 @@ pub fn run(context: &mut Context) {
     let input = context.get_input();
     let input = input.as_str();
 
-    let mut parts = input.split("\n\n");
-    let available_patterns: AvailablePatterns = parts.next().unwrap().parse().unwrap();
-    let target_patterns: Vec<Pattern> = parts
-        .next()
-        .unwrap()
-        .lines()
-        .map(|line| line.parse())
-        .collect::<Result<Vec<_>, _>>()
-        .unwrap();
+    let [available_patterns, target_patterns] = parse::sections_n(input).unwrap();
+    let available_patterns: AvailablePatterns = available_patterns.parse().unwrap();
+    let target_patterns: Vec<Pattern> = parse::lines_in(input, target_patterns).unwrap();
 
     let possible_count: usize = target_patterns
         .iter()
@@ -32,6 +28,7 @@ pub fn run(context: &mut Context) {
         })
         .sum();
     println!("{} ways to do designs", possible_count);
+    context.set_part_2_answer(possible_count);
 }
 
 struct AvailablePatterns {
@@ -121,7 +118,7 @@ fn hash(target: &[char]) -> u64 {
 struct Pattern(Vec<char>);
 
 impl FromStr for Pattern {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(Self(s.trim().chars().collect()))
@@ -129,13 +126,13 @@ impl FromStr for Pattern {
 }
 
 impl FromStr for AvailablePatterns {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(Self {
             available: s
                 .split(',')
-                .map(|v| v.parse())
+                .map(|v| parse_nested(s, v))
                 .collect::<Result<Vec<_>, _>>()?,
         })
     }
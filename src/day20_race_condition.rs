@@ -1,12 +1,14 @@
+use crate::common::distances::DistanceField;
+use crate::common::grid_cell::{grid_cell, GridCell};
 use crate::common::models::{Direction, Grid, Point};
+use crate::common::parse_error::ParseError;
+use crate::common::search::{self, Path};
 use crate::common::{Context, InputProvider};
-use anyhow::Context as AnyhowContext;
 use std::collections::{BTreeMap, HashSet};
-use std::fmt::{Display, Formatter};
 use std::str::FromStr;
+use anyhow::Context as AnyhowContext;
 use rayon::iter::IntoParallelIterator;
 use rayon::iter::ParallelIterator;
-use utils::a_star::{self, a_star_search, ComputationResult, Options, Successor};
 
 //noinspection RsConstantConditionIf
 pub fn run(context: &mut Context) {
@@ -58,6 +60,7 @@ pub fn run(context: &mut Context) {
         );
     }
     println!("there are {} that save picoseconds", total_that_save);
+    context.set_part_2_answer(total_that_save);
 }
 
 enum Space {
@@ -71,6 +74,11 @@ impl Default for Space {
     }
 }
 
+grid_cell!(Space {
+    Empty => '.',
+    Wall => '#',
+});
+
 struct Racetrack {
     grid: Grid<Space>,
     start: Point<usize>,
@@ -78,30 +86,26 @@ struct Racetrack {
 }
 
 impl FromStr for Racetrack {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut start = None;
         let mut end = None;
-        let grid = Grid::from_str_with(s, |c, point| {
-            Ok(match c {
-                '#' => Some(Space::Wall),
-                '.' => None,
-                'S' => {
-                    start = Some(point.clone());
-                    None
-                }
-                'E' => {
-                    end = Some(point.clone());
-                    None
-                }
-                other => return Err(anyhow::anyhow!("invalid character {}", other)),
-            })
+        let grid = Grid::from_str_with(s, |c, point| match c {
+            'S' => {
+                start = Some(*point);
+                Ok(None)
+            }
+            'E' => {
+                end = Some(*point);
+                Ok(None)
+            }
+            other => Space::parse_char(s, point, other).map(Some),
         })?;
         Ok(Self {
             grid,
-            start: start.context("no start position")?,
-            end: end.context("no end position")?,
+            start: start.ok_or_else(|| ParseError::missing(s, "a start position"))?,
+            end: end.ok_or_else(|| ParseError::missing(s, "an end position"))?,
         })
     }
 }
@@ -110,10 +114,10 @@ impl Racetrack {
     pub fn solve(
         &self,
         ignore_spaces: &HashSet<Point<usize>>,
-    ) -> a_star::Result<ComputationResult<Point<usize>, usize>> {
-        let start = self.start.clone();
+    ) -> Option<Path<Point<usize>, usize>> {
+        let start = self.start;
         let end = &self.end;
-        a_star_search(
+        search::a_star(
             start,
             |node| {
                 Direction::directions()
@@ -123,26 +127,45 @@ impl Racetrack {
                             !matches!(value, Space::Wall) || ignore_spaces.contains(pos)
                         })
                     })
-                    .map(|new_point| Successor::new(new_point, 1))
-                    .collect()
+                    .map(|new_point| (new_point, 1))
+                    .collect::<Vec<_>>()
+            },
+            |node| node.manhattan_distance(end),
+            |node| node == end,
+        )
+    }
+    // searches the contracted track, the walls leave few junctions besides start and end
+    fn track(&self) -> anyhow::Result<Vec<Point<usize>>> {
+        let graph = self.grid.junction_graph(
+            |_, value| !matches!(value, Space::Wall),
+            [self.start, self.end],
+        );
+        let start = graph.index_of(&self.start).unwrap();
+        let end = graph.index_of(&self.end).unwrap();
+        let path = search::dijkstra(
+            (start, None),
+            |&(junction, _): &(usize, Option<usize>)| {
+                graph
+                    .corridors_from(junction)
+                    .map(|(id, corridor)| ((corridor.to, Some(id)), corridor.length))
+                    .collect::<Vec<_>>()
             },
-            |details| details.current_node.manhattan_distance(&end),
-            |left| left == end,
-            None,
+            |&(junction, _)| junction == end,
         )
+        .context("racetrack has no path")?;
+        Ok(graph.expand(path.nodes.iter().filter_map(|&(_, corridor)| corridor)))
     }
-    pub fn solve_simple(&self, ignore_spaces: &HashSet<Point<usize>>) -> a_star::Result<usize> {
-        self.solve(ignore_spaces)
-            .map(|result| result.shortest_path_cost)
+    pub fn solve_simple(&self, ignore_spaces: &HashSet<Point<usize>>) -> Option<usize> {
+        self.solve(ignore_spaces).map(|result| result.cost)
     }
     pub fn solve_all_cheating(&self, max_score: usize) -> anyhow::Result<BTreeMap<usize, usize>> {
         let solutions = self
             .grid
-            .iter()
+            .par_iter()
             .filter(|(_, value)| matches!(value, Space::Wall))
             .filter_map(|(ignore_space, _)| {
-                let set = HashSet::from([ignore_space.clone()]);
-                self.solve_simple(&set).ok().and_then(|score| {
+                let set = HashSet::from([ignore_space]);
+                self.solve_simple(&set).and_then(|score| {
                     if score <= max_score {
                         Some((score, ignore_space))
                     } else {
@@ -172,7 +195,7 @@ impl Racetrack {
         &self,
         max_score: usize,
     ) -> anyhow::Result<BTreeMap<usize, usize>> {
-        let solution_path = self.solve(&HashSet::default())?.shortest_path;
+        let solution_path = self.track()?;
         let wormholes: Vec<_> = 
         (0..solution_path.len() - 1)
             .flat_map(|i| (i + 1..solution_path.len())
@@ -181,10 +204,14 @@ impl Racetrack {
             .filter_map(|(i, j)| Wormhole::try_from(&solution_path[i], &solution_path[j]))
             .collect();
         log::debug!("working with {} wormholes", wormholes.len());
-        
+
+        let passable = |_: &Point<usize>, value: &Space| !matches!(value, Space::Wall);
+        let from_start = self.grid.distances_from(&self.start, passable);
+        let from_end = self.grid.distances_from(&self.end, passable);
+
         let results: Vec<_> = wormholes.into_par_iter()
             .filter_map(|wormhole| {
-                self.solve_with_wormhole(max_score, wormhole).ok()
+                self.solve_with_wormhole(max_score, wormhole, &from_start, &from_end).ok()
             })
             .collect();
         let result = results.into_iter()
@@ -195,11 +222,16 @@ impl Racetrack {
             
         Ok(result)
     }
-    fn solve_with_wormhole(&self, max_score: usize, wormhole: Wormhole) -> anyhow::Result<usize> {
-        let start = self.start.clone();
-        let end = &self.end;
-
-        let mut total = self.solve_stage(start, &wormhole.from)?;
+    fn solve_with_wormhole(
+        &self,
+        max_score: usize,
+        wormhole: Wormhole,
+        from_start: &DistanceField,
+        from_end: &DistanceField,
+    ) -> anyhow::Result<usize> {
+        let mut total = from_start
+            .get(&wormhole.from)
+            .context("no path to the wormhole")?;
         if total > max_score {
             return Err(anyhow::anyhow!("too high"));
         }
@@ -207,39 +239,15 @@ impl Racetrack {
         if total > max_score {
             return Err(anyhow::anyhow!("too high"));
         }
-        total += self.solve_stage(wormhole.to.clone(), end)?;
+        total += from_end
+            .get(&wormhole.to)
+            .context("no path from the wormhole")?;
         if total > max_score {
             return Err(anyhow::anyhow!("too high"));
         }
 
         Ok(total)
     }
-    fn solve_stage(&self, from: Point<usize>, to: &Point<usize>) -> anyhow::Result<usize> {
-        if &from == to {
-            return Ok(0);
-        }
-        let result = a_star_search(
-            from,
-            |node| {
-                Direction::directions()
-                    .into_iter()
-                    .filter_map(|direction| {
-                        self.grid
-                            .try_move_if(node, direction, |_, value| !matches!(value, Space::Wall))
-                    })
-                    .map(|new_point| Successor::new(new_point, 1))
-                    .collect()
-            },
-            |details| {
-                details
-                    .current_node
-                    .manhattan_distance(&to)
-            },
-            |left| left == to,
-            Some(&Options::default().with_no_logs()),
-        )?;
-        Ok(result.shortest_path_cost)
-    }
 }
 
 struct Wormhole {
@@ -251,8 +259,8 @@ impl Wormhole {
     pub fn try_from(from: &Point<usize>, to: &Point<usize>) -> Option<Self> {
         if from.manhattan_distance(to) <= 20 {
             Some(Self {
-                from: from.clone(),
-                to: to.clone(),
+                from: *from,
+                to: *to,
             })
         } else {
             None
@@ -263,15 +271,6 @@ impl Wormhole {
     }
 }
 
-impl Display for Space {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Space::Empty => write!(f, "."),
-            Space::Wall => write!(f, "#"),
-        }
-    }
-}
-
 fn get_test_inputs() -> impl Iterator<Item = Box<InputProvider>> {
     ["###############
 #...#...#.....#
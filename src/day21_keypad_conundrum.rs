@@ -1,4 +1,6 @@
 use crate::common::models::{Direction, Point};
+use crate::common::parse_error::ParseError;
+use crate::common::parse;
 use crate::common::{Context, InputProvider};
 use itertools::Itertools;
 use std::borrow::Cow;
@@ -12,11 +14,7 @@ pub fn run(context: &mut Context) {
     let input = context.get_input();
     let input = input.as_str();
 
-    let door_codes: Vec<NumericSequence> = input
-        .lines()
-        .map(|line| line.parse())
-        .collect::<Result<Vec<_>, _>>()
-        .unwrap();
+    let door_codes: Vec<NumericSequence> = parse::lines(input).unwrap();
 
     let mut solver = Solver::default();
     let sum_complexities: usize = door_codes
@@ -36,6 +34,7 @@ pub fn run(context: &mut Context) {
         })
         .sum();
     println!("sum complexities 3 robots: {}", sum_complexities);
+    context.set_part_1_answer(sum_complexities);
 
     let robots = 26;
     let sum_complexities: usize = door_codes
@@ -55,6 +54,7 @@ pub fn run(context: &mut Context) {
         .sum();
 
     println!("sum complexities {} robots: {}", robots, sum_complexities);
+    context.set_part_2_answer(sum_complexities);
 }
 const PEEK_DISTANCE: usize = 5;
 fn find_next(
@@ -557,12 +557,15 @@ impl Display for DirectionalSequence {
 }
 
 impl FromStr for DirectionalSequence {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(Self(
-            s.chars()
-                .map(|c| c.try_into())
+            s.char_indices()
+                .map(|(i, c)| {
+                    c.try_into()
+                        .map_err(|_| ParseError::at(s, i, format!("invalid character '{}'", c)))
+                })
                 .collect::<Result<Vec<_>, _>>()?,
         ))
     }
@@ -588,7 +591,7 @@ impl Display for DirectionalValue {
 }
 
 impl TryFrom<char> for DirectionalValue {
-    type Error = anyhow::Error;
+    type Error = ();
 
     fn try_from(value: char) -> Result<Self, Self::Error> {
         Ok(Self(match value {
@@ -597,7 +600,7 @@ impl TryFrom<char> for DirectionalValue {
             '^' => Some(Direction::Up),
             '>' => Some(Direction::Right),
             'v' => Some(Direction::Down),
-            other => return Err(anyhow::anyhow!("invalid character {}", other)),
+            _ => return Err(()),
         }))
     }
 }
@@ -613,26 +616,27 @@ impl Display for NumericValue {
 }
 
 impl TryFrom<char> for NumericValue {
-    type Error = anyhow::Error;
+    type Error = ();
 
     fn try_from(value: char) -> Result<Self, Self::Error> {
         Ok(Self(match value {
             '0'..='9' => Some(value.to_digit(10).unwrap() as u8),
             'A' => None,
-            other => {
-                return Err(anyhow::anyhow!("invalid character {}", other));
-            }
+            _ => return Err(()),
         }))
     }
 }
 
 impl FromStr for NumericSequence {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(Self(
-            s.chars()
-                .map(|c| c.try_into())
+            s.char_indices()
+                .map(|(i, c)| {
+                    c.try_into()
+                        .map_err(|_| ParseError::at(s, i, format!("invalid character '{}'", c)))
+                })
                 .collect::<Result<Vec<_>, _>>()?,
         ))
     }
@@ -1,7 +1,8 @@
+use crate::common::parse_error::{parse_str, ParseError};
+use crate::common::parse;
 use crate::common::{Context, InputProvider};
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
-use std::num::ParseIntError;
 use std::str::FromStr;
 
 pub fn run(context: &mut Context) {
@@ -10,11 +11,7 @@ pub fn run(context: &mut Context) {
     let input = context.get_input();
     let input = input.as_str();
 
-    let mut secret_numbers: Vec<SecretNumber> = input
-        .lines()
-        .map(|line| line.parse())
-        .collect::<Result<Vec<_>, _>>()
-        .unwrap();
+    let mut secret_numbers: Vec<SecretNumber> = parse::lines(input).unwrap();
     let mut aggregators: Vec<FourStepAggregator> = vec![Default::default(); secret_numbers.len()];
 
     secret_numbers
@@ -30,6 +27,7 @@ pub fn run(context: &mut Context) {
         });
     let sum: i64 = secret_numbers.iter().map(|n| n.secret).sum();
     println!("sum of 2000th secret number: {}", sum);
+    context.set_part_1_answer(sum);
 
     let all_changes: HashMap<FourStepChange, i64> = aggregators
         .iter()
@@ -50,6 +48,7 @@ pub fn run(context: &mut Context) {
         .unwrap();
 
     println!("max bananas: {:?} {}", max_change.0, max);
+    context.set_part_2_answer(max);
 }
 
 #[derive(Clone, Default)]
@@ -126,10 +125,10 @@ impl SecretNumber {
 }
 
 impl FromStr for SecretNumber {
-    type Err = ParseIntError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self::new(s.parse()?))
+        Ok(Self::new(parse_str(s, s)?))
     }
 }
 
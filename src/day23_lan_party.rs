@@ -1,5 +1,6 @@
+use crate::common::parse_error::ParseError;
+use crate::common::parse;
 use crate::common::{Context, InputProvider};
-use anyhow::Context as AnyhowContext;
 use derivative::Derivative;
 use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Display, Formatter};
@@ -12,11 +13,7 @@ pub fn run(context: &mut Context) {
     let input = context.get_input();
     let input = input.as_str();
 
-    let connections: Vec<Connection> = input
-        .lines()
-        .map(|line| line.parse())
-        .collect::<Result<Vec<_>, _>>()
-        .unwrap();
+    let connections: Vec<Connection> = parse::lines(input).unwrap();
 
     let mut groups_of_three = HashSet::new();
     for (i, connection) in connections.iter().take(connections.len() - 1).enumerate() {
@@ -32,6 +29,7 @@ pub fn run(context: &mut Context) {
         .map(|group| log::debug!("3: {}", group))
         .count();
     println!("groups that start with t: {}", groups_with_t);
+    context.set_part_1_answer(groups_with_t);
 
     let parties = Parties {
         connections: ConnectionsIndexed::new(&connections),
@@ -41,6 +39,7 @@ pub fn run(context: &mut Context) {
     println!("largest group is size {}", largest.len());
     let largest: Vec<_> = largest.into_iter().map(|v| v.to_string()).collect();
     println!("password: {}", largest.join(","));
+    context.set_part_2_answer(largest.join(","));
 }
 
 struct Parties {
@@ -257,13 +256,15 @@ impl Computer {
 }
 
 impl FromStr for Connection {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut parts = s.split('-');
-        let mut get_next = || -> anyhow::Result<Computer> {
-            let next = parts.next().context("no dash in connection string")?;
-            Computer::new(next)
+        let mut get_next = || -> Result<Computer, ParseError> {
+            let next = parts
+                .next()
+                .ok_or_else(|| ParseError::missing(s, "a dash in connection string"))?;
+            Computer::new(next).map_err(|e| ParseError::at_str(s, next, e.to_string()))
         };
         Ok(Self::new(get_next()?, get_next()?))
     }
@@ -1,3 +1,5 @@
+use crate::common::parse;
+use crate::common::parse_error::ParseError;
 use crate::common::{Context, InputProvider};
 use anyhow::Context as AnyhowContext;
 use colored::*;
@@ -16,23 +18,13 @@ pub fn run(context: &mut Context) {
     let input = context.get_input();
     let input = input.as_str();
 
-    let mut input = input.split("\n\n");
-    let inputs: Vec<Wire> = input
-        .next()
-        .unwrap()
-        .lines()
-        .map(|line| line.parse())
-        .collect::<Result<Vec<_>, _>>()
-        .unwrap();
-    let gates: Vec<Gate> = input
-        .next()
-        .unwrap()
-        .lines()
-        .map(|line| line.parse())
-        .collect::<Result<Vec<_>, _>>()
-        .unwrap();
+    let [inputs, gates] = parse::sections_n(input).unwrap();
+    let inputs: Vec<Wire> = parse::lines_in(input, inputs).unwrap();
+    let gates: Vec<Gate> = parse::lines_in(input, gates).unwrap();
     let mut circuit = Circuit::new(inputs.clone().into_iter(), gates.clone().into_iter());
-    println!("output: {}", circuit.get_output("z").unwrap());
+    let output = circuit.get_output("z").unwrap();
+    println!("output: {}", output);
+    context.set_part_1_answer(output);
 
     let
         //mut 
@@ -49,6 +41,7 @@ pub fn run(context: &mut Context) {
     let mut password = ["hbs", "kfp", "z18", "dhq", "z22", "pdg", "z27", "jcp"];
     password.sort();
     println!("password: {}", password.join(","));
+    context.set_part_2_answer(password.join(","));
 }
 
 fn print_circuit(circuit: &Circuit, output_bits: usize) {
@@ -306,30 +299,51 @@ impl Operation {
 }
 
 impl FromStr for Wire {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.split(':');
-        let name = parts.next().context("empty string")?.trim().to_string();
-        let value = parts.next().context("no value found")?.trim() == "1";
+        let (name, value) = parse::key_value(s, ":")?;
+        let name = name.to_string();
+        let value = match value {
+            "1" => true,
+            "0" => false,
+            other => {
+                return Err(ParseError::at_str(
+                    s,
+                    other,
+                    format!("invalid wire value '{}'", other),
+                ))
+            }
+        };
         Ok(Self { name, value })
     }
 }
 
 impl FromStr for Gate {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut parts = s.split_whitespace();
-        let a = parts.next().context("a")?.to_string().into();
-        let operation = match parts.next().context("OP")? {
+        let mut next = |what: &str| parts.next().ok_or_else(|| ParseError::missing(s, what));
+        let a = next("first input wire")?.to_string().into();
+        let operation = match next("an operation")? {
             "XOR" => Operation::Xor,
             "OR" => Operation::Or,
             "AND" => Operation::And,
-            other => return Err(anyhow::anyhow!("invalid operation {}", other)),
+            other => {
+                return Err(ParseError::at_str(
+                    s,
+                    other,
+                    format!("invalid operation '{}'", other),
+                ))
+            }
         };
-        let b = parts.next().context("b")?.to_string().into();
-        let output = parts.nth(1).context("output")?.to_string();
+        let b = next("second input wire")?.to_string().into();
+        let arrow = next("'->'")?;
+        if arrow != "->" {
+            return Err(ParseError::at_str(s, arrow, "expected '->'"));
+        }
+        let output = next("an output wire")?.to_string();
         Ok(Self {
             operation,
             a,
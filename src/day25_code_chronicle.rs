@@ -1,3 +1,7 @@
+use crate::common::models::{Grid, Point};
+use crate::common::parse;
+use crate::common::parse_error::ParseError;
+use crate::common::pattern::Pattern;
 use crate::common::{Context, InputProvider};
 use std::fmt::{Display, Formatter};
 
@@ -14,70 +18,68 @@ pub fn run(context: &mut Context) {
         .iter()
         .flat_map(|lock| keys.iter().map(move |key| (lock, key)))
     {
-        if let Some(overlap) = key.key_overlaps_lock(lock) {
-            log::debug!(
-                "Lock {} and key {} overlap in column {}",
-                lock,
-                key,
-                overlap
-            );
+        if let Some(overlap) = key.overlap_with_lock(lock) {
+            log::debug!("Lock {} and key {} overlap at {}", lock, key, overlap);
         } else {
             log::debug!("Lock {} and key {} fit!", lock, key);
             fit += 1;
         }
     }
     println!("unique lock/key pairs that fit together: {}", fit);
+    context.set_part_1_answer(fit);
 }
 
 fn parse_locks_and_keys(input: &str) -> (Vec<LockOrKey>, Vec<LockOrKey>) {
     let mut locks = Vec::new();
     let mut keys = Vec::new();
-    for input in input.split("\n\n") {
-        let mut counts = [0u32; 5];
-        for line in input.lines() {
-            for (i, char) in line.chars().enumerate() {
-                match char {
-                    '.' => {}
-                    '#' => {
-                        counts[i] += 1;
-                    }
-                    other => panic!("invalid char '{}'", other),
-                }
-            }
+    for input in parse::sections(input) {
+        let grid = Grid::from_str_with(input, |c, point| match c {
+            '.' => Ok(Some(false)),
+            '#' => Ok(Some(true)),
+            other => Err(ParseError::invalid_char(input, point, other)),
+        })
+        .unwrap();
+        if grid.len_x() != 5 {
+            panic!("invalid width {}", grid.len_x());
         }
-        for count in counts.iter_mut() {
+        let mut counts = [0u32; 5];
+        for (count, column) in counts.iter_mut().zip(grid.columns()) {
+            *count = column.filter(|&&filled| filled).count() as u32;
             if *count == 0 || *count > 6 {
                 panic!("invalid count {}", count);
             }
             *count -= 1;
         }
-        match input.lines().next() {
-            None => continue,
-            Some(".....") => keys.push(LockOrKey(counts)),
-            Some("#####") => locks.push(LockOrKey(counts)),
-            Some(other) => panic!("invalid start of lock or key: '{}'", other),
+        match grid.row(0) {
+            [false, false, false, false, false] => keys.push(LockOrKey { counts, grid }),
+            [true, true, true, true, true] => locks.push(LockOrKey { counts, grid }),
+            _ => panic!(
+                "invalid start of lock or key: '{}'",
+                input.lines().next().unwrap()
+            ),
         }
     }
 
     (locks, keys)
 }
 
-struct LockOrKey([u32; 5]);
+struct LockOrKey {
+    counts: [u32; 5],
+    grid: Grid<bool>,
+}
 
 impl LockOrKey {
-    pub fn key_overlaps_lock(&self, lock: &Self) -> Option<usize> {
-        for (i, (key, lock)) in self.0.iter().zip(lock.0.iter()).enumerate() {
-            if key + lock > 5 {
-                return Some(i);
-            }
-        }
-        None
+    // the filled cells of the key must all be empty in the lock, anything else may be filled
+    pub fn overlap_with_lock(&self, lock: &Self) -> Option<Point<usize>> {
+        let pattern = Pattern::new(self.grid.map(|_, &filled| filled.then_some(false)));
+        let overlap = pattern.mismatches(&lock.grid, &Point::new(0, 0)).next();
+        overlap
     }
 }
 
 impl Display for LockOrKey {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0.map(|v| v.to_string()).join(","))
+        write!(f, "{}", self.counts.map(|v| v.to_string()).join(","))
     }
 }
 
@@ -1,3 +1,4 @@
+use common::InputProvider;
 use utils::timer::Timer;
 
 mod common;
@@ -26,6 +27,21 @@ mod day22_monkey_market;
 mod day23_lan_party;
 mod day24_crossed_wires;
 mod day25_code_chronicle;
+mod report;
+
+pub struct Day {
+    pub module: &'static str,
+    pub run: fn(&mut common::Context),
+}
+
+macro_rules! days {
+    ($($module:ident),* $(,)?) => {
+        &[$(Day {
+            module: stringify!($module),
+            run: $module::run,
+        }),*]
+    };
+}
 
 fn main() {
     let mut context = common::Context::default();
@@ -36,9 +52,21 @@ fn main() {
         }
     }
     //context.set_testing(0);
+    if let Ok(image_dir) = std::env::var("APP_IMAGES") {
+        context.set_image_dir(image_dir);
+    }
+    if let Ok(delay) = std::env::var("APP_PLAYBACK") {
+        if let Ok(delay) = delay.parse() {
+            context.set_playback_delay(std::time::Duration::from_millis(delay));
+        }
+    }
+
+    let is_report = std::env::args().nth(1).as_deref() == Some("report");
 
     if std::env::var("RUST_LOG").is_err() {
-        if context.is_testing() {
+        if is_report {
+            std::env::set_var("RUST_LOG", "warn");
+        } else if context.is_testing() {
             std::env::set_var("RUST_LOG", "trace");
         } else {
             std::env::set_var("RUST_LOG", "info");
@@ -49,49 +77,60 @@ fn main() {
 
     let days = days();
 
+    if is_report {
+        let options = report::ReportOptions::from_args(std::env::args().skip(2))
+            .expect("Failed to parse report options");
+        report::run(days, &options, input_file).expect("Failed to generate report");
+        return;
+    }
+
     let day: usize = if let Some(arg1) = std::env::args().nth(1) {
         arg1.parse().expect("Failed to parse day number")
     } else {
         days.len()
     };
 
-    context.set_text_input(Box::new(move || {
-        std::fs::read_to_string(format!("input/{:02}.txt", day))
-            .unwrap_or_else(|_| panic!("Failed to read input file input/{:02}.txt", day))
-            .into()
-    }));
-    let run = days[day - 1];
+    context.set_text_input(input_file(day));
+    let run = days[day - 1].run;
 
     println!("Running day {}\n", day);
     run(&mut context);
 }
 
-fn days() -> &'static [fn(&mut common::Context)] {
-    &[
-        day01_historian_hysteria::run,
-        day02_red_nosed_reports::run,
-        day03_mull_it_over::run,
-        day04_ceres_search::run,
-        day05_print_queue::run,
-        day06_guard_gallivant::run,
-        day07_bridge_repair::run,
-        day08_resonant_collinearity::run,
-        day09_disk_fragmenter::run,
-        day10_hoof_it::run,
-        day11_plutonian_pebbles::run,
-        day12_garden_groups::run,
-        day13_claw_contraption::run,
-        day14_restroom_redoubt::run,
-        day15_warehouse_woes::run,
-        day16_reindeer_maze::run,
-        day17_chronospatial_computer::run,
-        day18_ram_run::run,
-        day19_linen_layout::run,
-        day20_race_condition::run,
-        day21_keypad_conundrum::run,
-        day22_monkey_market::run,
-        day23_lan_party::run,
-        day24_crossed_wires::run,
-        day25_code_chronicle::run,
+fn input_file(day: usize) -> Box<InputProvider> {
+    Box::new(move || {
+        std::fs::read_to_string(format!("input/{:02}.txt", day))
+            .unwrap_or_else(|_| panic!("Failed to read input file input/{:02}.txt", day))
+            .into()
+    })
+}
+
+fn days() -> &'static [Day] {
+    days![
+        day01_historian_hysteria,
+        day02_red_nosed_reports,
+        day03_mull_it_over,
+        day04_ceres_search,
+        day05_print_queue,
+        day06_guard_gallivant,
+        day07_bridge_repair,
+        day08_resonant_collinearity,
+        day09_disk_fragmenter,
+        day10_hoof_it,
+        day11_plutonian_pebbles,
+        day12_garden_groups,
+        day13_claw_contraption,
+        day14_restroom_redoubt,
+        day15_warehouse_woes,
+        day16_reindeer_maze,
+        day17_chronospatial_computer,
+        day18_ram_run,
+        day19_linen_layout,
+        day20_race_condition,
+        day21_keypad_conundrum,
+        day22_monkey_market,
+        day23_lan_party,
+        day24_crossed_wires,
+        day25_code_chronicle,
     ]
 }
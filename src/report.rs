@@ -0,0 +1,265 @@
+use crate::common::{Context, InputProvider};
+use crate::Day;
+use anyhow::Context as AnyhowContext;
+use std::fmt::{Display, Formatter, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+const README_PATH: &str = "README.md";
+const SECTION_START: &str = "<!-- report:start -->";
+const SECTION_END: &str = "<!-- report:end -->";
+const LAST_DAY: usize = 25;
+
+#[derive(Default)]
+pub struct ReportOptions {
+    reveal: bool,
+    output: Option<String>,
+}
+
+impl ReportOptions {
+    pub fn from_args(mut args: impl Iterator<Item = String>) -> anyhow::Result<Self> {
+        let mut options = Self::default();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--reveal" => options.reveal = true,
+                "--output" => {
+                    options.output = Some(args.next().context("--output requires a file path")?)
+                }
+                other => return Err(anyhow::anyhow!("unknown report option '{}'", other)),
+            }
+        }
+        Ok(options)
+    }
+}
+
+pub fn run(
+    days: &[Day],
+    options: &ReportOptions,
+    input_file: impl Fn(usize) -> Box<InputProvider>,
+) -> anyhow::Result<()> {
+    let results: Vec<DayResult> = days
+        .iter()
+        .enumerate()
+        .map(|(i, day)| {
+            println!("Running day {}", i + 1);
+            DayResult::run(i + 1, day, input_file(i + 1))
+        })
+        .collect();
+
+    let markdown = render(&results, options.reveal);
+
+    if let Some(output) = &options.output {
+        std::fs::write(output, format!("# Report\n\n{}", markdown))
+            .with_context(|| format!("failed to write {}", output))?;
+        println!("report written to {}", output);
+    } else {
+        let readme = std::fs::read_to_string(README_PATH)
+            .with_context(|| format!("failed to read {}", README_PATH))?;
+        std::fs::write(README_PATH, replace_section(&readme, &markdown))
+            .with_context(|| format!("failed to write {}", README_PATH))?;
+        println!("report written to {}", README_PATH);
+    }
+
+    Ok(())
+}
+
+struct DayResult {
+    day: usize,
+    title: String,
+    part_1: Option<String>,
+    part_2: Option<String>,
+    elapsed: Duration,
+    status: Status,
+}
+
+enum Status {
+    Complete,
+    Partial,
+    Unsolved,
+    Panicked(String),
+}
+
+impl DayResult {
+    pub fn run(day: usize, definition: &Day, input: Box<InputProvider>) -> Self {
+        let mut context = Context::default();
+        context.set_text_input(input);
+
+        let start = Instant::now();
+        let outcome = panic::catch_unwind(AssertUnwindSafe(|| (definition.run)(&mut context)));
+        let elapsed = start.elapsed();
+
+        let (part_1, part_2) = context.answers();
+        let status = match outcome {
+            Err(payload) => Status::Panicked(panic_message(payload.as_ref())),
+            Ok(()) => match (part_1, part_2) {
+                (Some(_), Some(_)) => Status::Complete,
+                (Some(_), None) if day == LAST_DAY => Status::Complete,
+                (None, None) => Status::Unsolved,
+                _ => Status::Partial,
+            },
+        };
+
+        Self {
+            day,
+            title: title_from_module(definition.module),
+            part_1: part_1.map(str::to_string),
+            part_2: part_2.map(str::to_string),
+            elapsed,
+            status,
+        }
+    }
+    fn stars(&self) -> usize {
+        self.part_1.iter().chain(self.part_2.iter()).count()
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Complete => write!(f, "complete"),
+            Status::Partial => write!(f, "partial"),
+            Status::Unsolved => write!(f, "unsolved"),
+            Status::Panicked(message) => write!(f, "panicked: {}", message.replace('|', "\\|")),
+        }
+    }
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+fn title_from_module(module: &str) -> String {
+    let name = module.split_once('_').map_or(module, |(_, name)| name);
+    name.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or(String::new(), |first| {
+                first.to_uppercase().chain(chars).collect()
+            })
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn format_answer(answer: Option<&String>, reveal: bool) -> String {
+    match answer {
+        None => "-".to_string(),
+        Some(answer) if reveal => format!("`{}`", answer),
+        Some(answer) => format!("`#{:016x}`", fnv1a(answer.as_bytes())),
+    }
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+fn render(results: &[DayResult], reveal: bool) -> String {
+    let mut markdown = String::new();
+    writeln!(
+        markdown,
+        "| Day | Title | Part 1 | Part 2 | Runtime | Status |"
+    )
+    .unwrap();
+    writeln!(
+        markdown,
+        "|----:|-------|--------|--------|--------:|--------|"
+    )
+    .unwrap();
+    for result in results {
+        writeln!(
+            markdown,
+            "| {} | {} | {} | {} | {} ms | {} |",
+            result.day,
+            result.title,
+            format_answer(result.part_1.as_ref(), reveal),
+            format_answer(result.part_2.as_ref(), reveal),
+            result.elapsed.as_millis(),
+            result.status
+        )
+        .unwrap();
+    }
+
+    let complete = results
+        .iter()
+        .filter(|result| matches!(result.status, Status::Complete))
+        .count();
+    let stars: usize = results.iter().map(DayResult::stars).sum();
+    let total: Duration = results.iter().map(|result| result.elapsed).sum();
+    writeln!(markdown).unwrap();
+    writeln!(
+        markdown,
+        "**Totals:** {}/{} days complete, {} answers, {} ms total runtime.",
+        complete,
+        results.len(),
+        stars,
+        total.as_millis()
+    )
+    .unwrap();
+    if !reveal {
+        writeln!(
+            markdown,
+            "\nAnswers are shown as FNV-1a hashes; run `cargo run --release report --reveal` to show them."
+        )
+        .unwrap();
+    }
+
+    markdown
+}
+
+fn replace_section(document: &str, section: &str) -> String {
+    let generated = format!("{}\n{}{}", SECTION_START, section, SECTION_END);
+    match (document.find(SECTION_START), document.find(SECTION_END)) {
+        (Some(start), Some(end)) if start < end => format!(
+            "{}{}{}",
+            &document[..start],
+            generated,
+            &document[end + SECTION_END.len()..]
+        ),
+        _ => format!("{}\n\n## Status\n{}\n", document.trim_end(), generated),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_title_from_module() {
+        assert_eq!(
+            title_from_module("day01_historian_hysteria"),
+            "Historian Hysteria"
+        );
+        assert_eq!(title_from_module("day18_ram_run"), "Ram Run");
+    }
+
+    #[test]
+    fn test_replace_section() {
+        let document = format!("# Title\n{}\nold\n{}\nafter\n", SECTION_START, SECTION_END);
+        let replaced = replace_section(&document, "new\n");
+        assert_eq!(
+            replaced,
+            format!("# Title\n{}\nnew\n{}\nafter\n", SECTION_START, SECTION_END)
+        );
+        assert_eq!(replace_section(&replaced, "new\n"), replaced);
+    }
+
+    #[test]
+    fn test_replace_section_appends_when_missing() {
+        let replaced = replace_section("# Title\n", "new\n");
+        assert_eq!(
+            replaced,
+            format!(
+                "# Title\n\n## Status\n{}\nnew\n{}\n",
+                SECTION_START, SECTION_END
+            )
+        );
+    }
+}